@@ -0,0 +1,57 @@
+use aesteve::AESteve;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const MESSAGES: usize = 10_000;
+
+/// Counts heap allocations and reallocations so the reserved and unreserved batch paths
+/// can be compared directly.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench_batch(c: &mut Criterion) {
+    let aesteve = AESteve::new(&[0x2bu8; 16]).unwrap();
+    let owned: Vec<String> = (0..MESSAGES).map(|i| format!("message number {i} of the batch")).collect();
+    let messages: Vec<&str> = owned.iter().map(String::as_str).collect();
+
+    println!(
+        "allocations for {MESSAGES} messages: unreserved={} encrypt_batch={}",
+        count_allocations(|| aesteve.encrypt_batch_unreserved(&messages)),
+        count_allocations(|| aesteve.encrypt_batch(&messages)),
+    );
+
+    let mut group = c.benchmark_group("batch_10k_messages");
+    group.bench_function("unreserved", |b| b.iter(|| aesteve.encrypt_batch_unreserved(black_box(&messages))));
+    group.bench_function("encrypt_batch", |b| b.iter(|| aesteve.encrypt_batch(black_box(&messages))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch);
+criterion_main!(benches);
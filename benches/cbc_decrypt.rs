@@ -0,0 +1,20 @@
+use aesteve::AESteve;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+const BUFFER_SIZE: usize = 16 * 1024 * 1024;
+
+fn bench_cbc_decrypt(c: &mut Criterion) {
+    let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+    let iv = [0x24u8; 16];
+    let ciphertext = aesteve.encrypt_cbc(&vec![0x5Au8; BUFFER_SIZE], &iv);
+
+    let mut group = c.benchmark_group("cbc_decrypt_16MiB");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(BUFFER_SIZE as u64));
+    group.bench_function("sequential", |b| b.iter(|| aesteve.decrypt_cbc_sequential(&ciphertext, &iv).unwrap()));
+    group.bench_function("parallel", |b| b.iter(|| aesteve.decrypt_cbc(&ciphertext, &iv).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_cbc_decrypt);
+criterion_main!(benches);
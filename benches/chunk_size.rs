@@ -0,0 +1,26 @@
+use aesteve::AESteve;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const BUFFER_SIZE: usize = 64 * 1024 * 1024;
+
+fn bench_chunk_size(c: &mut Criterion) {
+    let data = vec![0x5Au8; BUFFER_SIZE];
+
+    let mut group = c.benchmark_group("encrypt_64MiB");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(BUFFER_SIZE as u64));
+    group.bench_function("auto", |b| {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        b.iter(|| aesteve.encrypt_bytes(&data))
+    });
+    for blocks in [4, 64, 1024, 16384] {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap().with_chunk_size(blocks);
+        group.bench_with_input(BenchmarkId::new("blocks_per_task", blocks), &data, |b, data| {
+            b.iter(|| aesteve.encrypt_bytes(data))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_chunk_size);
+criterion_main!(benches);
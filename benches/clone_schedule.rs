@@ -0,0 +1,15 @@
+use aesteve::AESteve;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn bench_clone_schedule(c: &mut Criterion) {
+    let key = [0x2bu8; 16];
+    let aesteve = AESteve::new(&key).unwrap();
+
+    let mut group = c.benchmark_group("per_request_cipher");
+    group.bench_function("new", |b| b.iter(|| AESteve::new(black_box(&key)).unwrap()));
+    group.bench_function("clone", |b| b.iter(|| black_box(&aesteve).clone()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_clone_schedule);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+use aesteve::AESteve;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const MESSAGES: usize = 10_000;
+
+fn bench_cmac_small(c: &mut Criterion) {
+    let aesteve = AESteve::new(&[0x2bu8; 16]).unwrap();
+    let messages: Vec<[u8; 24]> = (0..MESSAGES).map(|i| [i as u8; 24]).collect();
+    let tag = [0u8; 16];
+
+    c.bench_function("cmac_10k_small_messages", |b| {
+        b.iter(|| {
+            for message in &messages {
+                black_box(aesteve.verify_cmac(black_box(message), &tag));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_cmac_small);
+criterion_main!(benches);
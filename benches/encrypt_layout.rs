@@ -0,0 +1,33 @@
+use aesteve::{AESteve, Padding, PaddingScheme};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rayon::prelude::*;
+
+const BUFFER_SIZE: usize = 256 * 1024 * 1024;
+
+/// The naive layout: one rayon item per block, the baseline for the cache-blocked tiles.
+fn encrypt_parallel_map(aesteve: &AESteve, data: &[u8]) -> Vec<u8> {
+    let mut padded = data.to_vec();
+    Padding::Iso7816.pad(&mut padded);
+    padded
+        .par_chunks_exact(16)
+        .flat_map_iter(|block| aesteve.encrypt_array(block.try_into().unwrap()))
+        .collect()
+}
+
+fn bench_encrypt_layout(c: &mut Criterion) {
+    let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+    let data = vec![0x5Au8; BUFFER_SIZE];
+
+    let mut group = c.benchmark_group("encrypt_256MiB");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(BUFFER_SIZE as u64));
+    group.bench_function("parallel_map", |b| b.iter(|| encrypt_parallel_map(&aesteve, &data)));
+    group.bench_function("cache_blocked", |b| b.iter(|| aesteve.encrypt_bytes_blocked(&data)));
+    group.bench_function("grouped_tasks", |b| {
+        b.iter(|| aesteve.encrypt_with_padding(&data, &Padding::Iso7816))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_encrypt_layout);
+criterion_main!(benches);
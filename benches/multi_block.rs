@@ -0,0 +1,19 @@
+use aesteve::AESteve;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+const BUFFER_SIZE: usize = 16 * 1024 * 1024;
+
+fn bench_multi_block(c: &mut Criterion) {
+    let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+    let data = vec![0x5Au8; BUFFER_SIZE];
+
+    let mut group = c.benchmark_group("encrypt_16MiB");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(BUFFER_SIZE as u64));
+    group.bench_function("single_block", |b| b.iter(|| aesteve.encrypt_blocks_single(&data)));
+    group.bench_function("four_blocks", |b| b.iter(|| aesteve.encrypt_blocks_grouped(&data)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_multi_block);
+criterion_main!(benches);
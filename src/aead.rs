@@ -0,0 +1,175 @@
+use crate::gcm::gcm_len_ok;
+use crate::AESteve;
+use aead::consts::{U0, U12, U16};
+use aead::generic_array::typenum::NonZero;
+use aead::generic_array::ArrayLength;
+use aead::{AeadCore, AeadInPlace, Key, KeyInit, KeySizeUser, Nonce, Tag};
+use std::marker::PhantomData;
+
+/// AES-128-GCM exposed through the RustCrypto [`aead`] traits.
+///
+/// Drops into code written against `aes-gcm`'s `Aes128Gcm`: 16-byte keys,
+/// 12-byte nonces and 16-byte tags appended to the ciphertext by [`aead::Aead`].
+/// Like `aes-gcm`'s `AesGcm`, the nonce size is a type parameter: any size other than
+/// the default 96 bits (e.g. `AESteveGcm<U16>`) derives the initial counter by GHASHing
+/// the nonce, as specified by NIST SP 800-38D.
+///
+/// SP 800-38D requires a nonce of at least one bit. An empty nonce would GHASH to the same
+/// initial counter for every message and reuse the keystream, so zero-length nonce sizes
+/// do not implement the `aead` traits:
+///
+/// ```compile_fail
+/// use aead::consts::U0;
+/// use aead::{Aead, KeyInit, Nonce};
+/// use aesteve::AESteveGcm;
+///
+/// let cipher = AESteveGcm::<U0>::new(&[0u8; 16].into());
+/// let ciphertext = cipher.encrypt(&Nonce::<AESteveGcm<U0>>::default(), &b"message"[..]);
+/// ```
+#[derive(Debug)]
+pub struct AESteveGcm<NonceSize = U12> {
+    cipher: AESteve,
+    nonce_size: PhantomData<NonceSize>,
+}
+
+impl<N> From<AESteve> for AESteveGcm<N> {
+    fn from(cipher: AESteve) -> Self {
+        AESteveGcm { cipher, nonce_size: PhantomData }
+    }
+}
+
+impl<N> KeySizeUser for AESteveGcm<N> {
+    type KeySize = U16;
+}
+
+impl<N> KeyInit for AESteveGcm<N> {
+    fn new(key: &Key<Self>) -> Self {
+        let cipher = AESteve::new(key).expect("Key<AESteveGcm> is always 16 bytes");
+        AESteveGcm::from(cipher)
+    }
+}
+
+impl<N: ArrayLength<u8> + NonZero> AeadCore for AESteveGcm<N> {
+    type NonceSize = N;
+    type TagSize = U16;
+    type CiphertextOverhead = U0;
+}
+
+impl<N: ArrayLength<u8> + NonZero> AeadInPlace for AESteveGcm<N> {
+    fn encrypt_in_place_detached(
+        &self,
+        nonce: &Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> aead::Result<Tag<Self>> {
+        if !gcm_len_ok(buffer.len()) {
+            return Err(aead::Error);
+        }
+        let tag = self.cipher.gcm_seal_in_place(nonce.as_ref(), associated_data, buffer);
+        Ok(tag.into())
+    }
+
+    fn decrypt_in_place_detached(
+        &self,
+        nonce: &Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &Tag<Self>,
+    ) -> aead::Result<()> {
+        self.cipher
+            .gcm_open_in_place(nonce.as_ref(), associated_data, buffer, tag.as_ref())
+            .map_err(|_| aead::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aead::consts::{U60, U8};
+    use aead::{Aead, Payload};
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len()).step_by(2).map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_aead_nist_vector() {
+        // NIST GCM specification, test case 4.
+        let key = hex("feffe9928665731c6d6a8f9467308308");
+        let nonce = hex("cafebabefacedbaddecaf888");
+        let aad = hex("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+        let plaintext = hex(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+        );
+        let expected = hex(
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+             21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091\
+             5bc94fbc3221a5db94fae95ae7121a47",
+        );
+
+        let cipher = AESteveGcm::new_from_slice(&key).unwrap();
+        let nonce = Nonce::<AESteveGcm>::from_slice(&nonce);
+        let ciphertext = cipher.encrypt(nonce, Payload { msg: &plaintext, aad: &aad }).unwrap();
+        assert_eq!(ciphertext, expected);
+
+        let decrypted = cipher.decrypt(nonce, Payload { msg: &ciphertext, aad: &aad }).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_aead_non_96_bit_nonces() {
+        // NIST GCM specification, test cases 5 (64-bit IV) and 6 (480-bit IV).
+        let key = hex("feffe9928665731c6d6a8f9467308308");
+        let aad = hex("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+        let plaintext = hex(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+        );
+
+        let cipher = AESteveGcm::<U8>::new_from_slice(&key).unwrap();
+        let nonce = hex("cafebabefacedbad");
+        let expected = hex(
+            "61353b4c2806934a777ff51fa22a4755699b2a714fcdc6f83766e5f97b6c7423\
+             73806900e49f24b22b097544d4896b424989b5e1ebac0f07c23f4598\
+             3612d2e79e3b0785561be14aaca2fccb",
+        );
+        let payload = Payload { msg: &plaintext, aad: &aad };
+        assert_eq!(cipher.encrypt(Nonce::<AESteveGcm<U8>>::from_slice(&nonce), payload).unwrap(), expected);
+
+        let cipher = AESteveGcm::<U60>::new_from_slice(&key).unwrap();
+        let nonce = hex(
+            "9313225df88406e555909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728\
+             c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39b",
+        );
+        let expected = hex(
+            "8ce24998625615b603a033aca13fb894be9112a5c3a211a8ba262a3cca7e2ca7\
+             01e4a9a4fba43c90ccdcb281d48c7c6fd62875d2aca417034c34aee5\
+             619cc5aefffe0bfa462af43c1699d050",
+        );
+        let nonce = Nonce::<AESteveGcm<U60>>::from_slice(&nonce);
+        let ciphertext = cipher.encrypt(nonce, Payload { msg: &plaintext, aad: &aad }).unwrap();
+        assert_eq!(ciphertext, expected);
+        assert_eq!(cipher.decrypt(nonce, Payload { msg: &ciphertext, aad: &aad }).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_aead_16_byte_nonce() {
+        // Python `cryptography` AESGCM, key as in test case 4, nonce 00..0f, no AAD.
+        let cipher = AESteveGcm::<U16>::new_from_slice(&hex("feffe9928665731c6d6a8f9467308308")).unwrap();
+        let nonce_bytes: Vec<u8> = (0u8..16).collect();
+        let nonce = Nonce::<AESteveGcm<U16>>::from_slice(&nonce_bytes);
+        let expected = hex("396b6cceec711cb4cb60894604bd023851e7c7036ef6161be094e68095c2a5b5bb43");
+        assert_eq!(cipher.encrypt(nonce, b"sixteen-byte nonce".as_ref()).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_aead_rejects_tampering() {
+        let cipher = AESteveGcm::from(AESteve::new(&[0u8; 16]).unwrap());
+        let nonce = Nonce::<AESteveGcm>::from_slice(&[0u8; 12]);
+        let mut ciphertext = cipher.encrypt(nonce, b"drop-in for aes-gcm".as_ref()).unwrap();
+        ciphertext[0] ^= 1;
+        assert!(cipher.decrypt(nonce, ciphertext.as_ref()).is_err());
+        assert!(cipher.decrypt(nonce, &ciphertext[..15]).is_err());
+    }
+}
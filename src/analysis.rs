@@ -0,0 +1,82 @@
+use crate::{AESteve, Padding};
+use std::collections::{HashMap, HashSet};
+
+fn identical_block_pairs(ciphertext: &[u8]) -> Vec<(usize, usize)> {
+    let mut seen: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    let mut pairs = Vec::new();
+    for (index, block) in ciphertext.chunks_exact(16).enumerate() {
+        let earlier = seen.entry(block).or_default();
+        pairs.extend(earlier.iter().map(|&first| (first, index)));
+        earlier.push(index);
+    }
+    pairs
+}
+
+/// Heuristically detects ECB-mode ciphertext: returns `true` if any two 16-byte blocks
+/// are identical.
+///
+/// Needs no key. A repeated block in ciphertext from any decent mode with a fresh IV or
+/// nonce is astronomically unlikely, so a hit is strong evidence of ECB. A miss proves
+/// nothing: ECB over plaintext without repeated blocks looks random too. Any trailing
+/// partial block is ignored.
+///
+/// # Arguments
+///
+/// * `ciphertext` - The raw ciphertext to inspect.
+///
+/// # Returns
+///
+/// * `bool` - `true` if some 16-byte block occurs more than once.
+pub fn looks_like_ecb(ciphertext: &[u8]) -> bool {
+    let mut seen = HashSet::new();
+    ciphertext.chunks_exact(16).any(|block| !seen.insert(block))
+}
+
+impl AESteve {
+    /// Encrypts `message` in ECB mode and reports which ciphertext blocks are identical.
+    ///
+    /// Identical ciphertext blocks mean identical plaintext blocks, which is exactly the
+    /// structure ECB leaks. Useful for demonstrating the weakness or auditing data.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The bytes to be encrypted (padded as by `encrypt_ecb`).
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(usize, usize)>` - Every pair of block indices `(i, j)` with `i < j` whose
+    ///   ciphertext blocks are equal, ordered by `j` then `i`.
+    pub fn ecb_block_collisions(&self, message: &[u8]) -> Vec<(usize, usize)> {
+        identical_block_pairs(&self.encrypt_with_padding(message, &Padding::Iso7816))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ecb_block_collisions() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let mut message = Vec::new();
+        message.extend_from_slice(b"YELLOW SUBMARINE");
+        message.extend_from_slice(b"ANOTHER 16 BYTES");
+        message.extend_from_slice(b"YELLOW SUBMARINE");
+        message.extend_from_slice(b"YELLOW SUBMARINE");
+
+        assert_eq!(aesteve.ecb_block_collisions(&message), vec![(0, 2), (0, 3), (2, 3)]);
+        assert!(aesteve.ecb_block_collisions(b"no repeated blocks here").is_empty());
+    }
+
+    #[test]
+    fn test_looks_like_ecb() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let message = b"YELLOW SUBMARINE".repeat(4);
+        assert!(looks_like_ecb(&aesteve.encrypt_with_padding(&message, &Padding::Iso7816)));
+
+        let random: [u8; 256] = crate::random_array();
+        assert!(!looks_like_ecb(&random));
+        assert!(!looks_like_ecb(&aesteve.encrypt_ctr(&message, &[0u8; 16])));
+        assert!(!looks_like_ecb(&[0u8; 31]));
+    }
+}
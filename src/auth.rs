@@ -0,0 +1,147 @@
+use crate::ct::{ct_eq_mask, ct_mask};
+use crate::kdf::derive_salted_keys;
+use crate::{random_array, wipe, AESError, AESteve};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+const INFO: &[u8] = b"AuthCipher v1";
+const IV_LEN: usize = 16;
+const TAG_LEN: usize = 32;
+
+/// AES-128-CTR and HMAC-SHA256 combined as encrypt-then-MAC under keys derived from
+/// one password.
+///
+/// Created with [`AESteve::authenticated_from_password`]. The MAC key is zeroed when
+/// dropped, as is the AES key schedule.
+pub struct AuthCipher {
+    cipher: AESteve,
+    mac_key: [u8; 32],
+}
+
+impl AESteve {
+    /// Derives an AES key and an HMAC key from `password` and builds an [`AuthCipher`].
+    ///
+    /// Both keys come from HKDF-SHA256 over `password` with `salt` as the HKDF salt,
+    /// expanded with distinct labels as in [`derive_keys`](crate::derive_keys). HKDF does
+    /// no key stretching, so this suits high-entropy passphrases; for passwords people
+    /// choose, prefer [`seal_with_password`](crate::seal_with_password)'s PBKDF2.
+    ///
+    /// # Arguments
+    ///
+    /// * `password` - The passphrase both keys are derived from.
+    /// * `salt` - A random, per-application or per-user salt.
+    ///
+    /// # Returns
+    ///
+    /// * `AuthCipher` - The combined encrypt-then-MAC cipher.
+    pub fn authenticated_from_password(password: &str, salt: &[u8]) -> AuthCipher {
+        let (mut aes_key, mac_key) = derive_salted_keys(salt, password.as_bytes(), INFO);
+        let cipher = AESteve::new(&aes_key).unwrap();
+        wipe(&mut aes_key);
+        AuthCipher { cipher, mac_key }
+    }
+}
+
+impl AuthCipher {
+    fn tag(&self, iv_and_ciphertext: &[u8]) -> [u8; TAG_LEN] {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.mac_key).expect("HMAC accepts any key length");
+        mac.update(iv_and_ciphertext);
+        mac.finalize().into_bytes().into()
+    }
+
+    /// Encrypts `plaintext` with AES-128-CTR under a random IV, then MACs the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `plaintext` - The data to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - `iv ‖ ciphertext ‖ tag`, where the tag is HMAC-SHA256 over
+    ///   `iv ‖ ciphertext`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system's random number generator is unavailable.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let iv: [u8; IV_LEN] = random_array();
+        let mut output = Vec::with_capacity(IV_LEN + plaintext.len() + TAG_LEN);
+        output.extend_from_slice(&iv);
+        output.extend_from_slice(&self.cipher.encrypt_ctr(plaintext, &iv));
+        let tag = self.tag(&output);
+        output.extend_from_slice(&tag);
+        output
+    }
+
+    /// Verifies and decrypts output of [`AuthCipher::encrypt`].
+    ///
+    /// The tag is checked in constant time and decryption runs either way; the plaintext
+    /// is zeroed before a failure is reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The `iv ‖ ciphertext ‖ tag` output of `encrypt`.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The plaintext.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::DecryptionFailed` if the password or salt is wrong, or the
+    /// ciphertext is truncated or tampered with.
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < IV_LEN + TAG_LEN {
+            return Err(AESError::DecryptionFailed);
+        }
+        let (authenticated, tag) = ciphertext.split_at(ciphertext.len() - TAG_LEN);
+        let (iv, body) = authenticated.split_at(IV_LEN);
+
+        let valid = ct_eq_mask(&self.tag(authenticated), tag);
+        let mut plaintext = self.cipher.encrypt_ctr(body, iv.try_into().unwrap());
+        ct_mask(&mut plaintext, valid);
+        if valid == 0x00 {
+            return Err(AESError::DecryptionFailed);
+        }
+        Ok(plaintext)
+    }
+}
+
+impl Drop for AuthCipher {
+    fn drop(&mut self) {
+        wipe(&mut self.mac_key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_cipher_round_trip() {
+        let auth = AESteve::authenticated_from_password("correct horse battery staple", b"app salt");
+        for plaintext in [&b""[..], b"encrypt-then-MAC", &[0x42; 100]] {
+            let ciphertext = auth.encrypt(plaintext);
+            assert_eq!(ciphertext.len(), IV_LEN + plaintext.len() + TAG_LEN);
+            assert_eq!(auth.decrypt(&ciphertext).unwrap(), plaintext);
+        }
+        let same = AESteve::authenticated_from_password("correct horse battery staple", b"app salt");
+        assert_eq!(same.decrypt(&auth.encrypt(b"derived twice")).unwrap(), b"derived twice");
+    }
+
+    #[test]
+    fn test_auth_cipher_wrong_password() {
+        let auth = AESteve::authenticated_from_password("correct horse battery staple", b"app salt");
+        let ciphertext = auth.encrypt(b"encrypt-then-MAC");
+
+        let wrong_password = AESteve::authenticated_from_password("battery staple", b"app salt");
+        let wrong_salt = AESteve::authenticated_from_password("correct horse battery staple", b"other salt");
+        assert!(matches!(wrong_password.decrypt(&ciphertext), Err(AESError::DecryptionFailed)));
+        assert!(matches!(wrong_salt.decrypt(&ciphertext), Err(AESError::DecryptionFailed)));
+
+        let mut tampered = ciphertext.clone();
+        tampered[IV_LEN] ^= 1;
+        assert!(matches!(auth.decrypt(&tampered), Err(AESError::DecryptionFailed)));
+        assert!(matches!(auth.decrypt(&ciphertext[..40]), Err(AESError::DecryptionFailed)));
+    }
+}
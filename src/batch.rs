@@ -0,0 +1,67 @@
+use crate::AESteve;
+use rayon::prelude::*;
+
+impl AESteve {
+    /// Encrypts many messages like [`AESteve::encrypt_ecb`], in parallel.
+    ///
+    /// Built for bulk jobs: the output `Vec` is reserved for all messages up front, and
+    /// each encoded string is allocated once at its exact length, computed from the
+    /// padded block count, so neither reallocates while being filled.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - The messages to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The encrypted messages in the configured encoding (Base64 by
+    ///   default), in input order.
+    pub fn encrypt_batch(&self, messages: &[&str]) -> Vec<String> {
+        self.encrypt_batch_sized(messages, true)
+    }
+
+    /// The [`AESteve::encrypt_batch`] pipeline, with the per-message buffers and strings
+    /// sized exactly up front when `reserve` is set, or grown on demand otherwise.
+    pub(crate) fn encrypt_batch_sized(&self, messages: &[&str], reserve: bool) -> Vec<String> {
+        let mut encrypted_messages = Vec::with_capacity(messages.len());
+        messages
+            .par_iter()
+            .map(|message| {
+                if !reserve {
+                    return self.encoding.encode(&self.encrypt_bytes(message.as_bytes()));
+                }
+                let ciphertext = self.encrypt_bytes_with_capacity(message.as_bytes(), self.padded_len(message.len()));
+                let mut encoded = String::with_capacity(self.encoding.encoded_len(ciphertext.len()));
+                self.encoding.encode_into(&ciphertext, &mut encoded);
+                encoded
+            })
+            .collect_into_vec(&mut encrypted_messages);
+        encrypted_messages
+    }
+
+    /// Returns the length of a `len`-byte message once its checksum and padding are appended.
+    fn padded_len(&self, len: usize) -> usize {
+        (len + if self.checksum { 4 } else { 0 }) / 16 * 16 + 16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_matches_single() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let messages = ["", "short", "exactly sixteen!", "a message spanning three AES blocks in all"];
+        let encrypted_messages = aesteve.encrypt_batch(&messages);
+        assert_eq!(encrypted_messages.len(), messages.len());
+        for (message, encrypted_message) in messages.iter().zip(&encrypted_messages) {
+            assert_eq!(encrypted_message, &aesteve.encrypt_ecb(message.to_string()).unwrap());
+            assert_eq!(encrypted_message.capacity(), encrypted_message.len());
+        }
+        assert_eq!(aesteve.encrypt_batch_sized(&messages, false), encrypted_messages);
+
+        let checked = aesteve.with_checksum(true).with_envelope(true);
+        assert_eq!(checked.encrypt_batch(&messages), checked.encrypt_batch_sized(&messages, false));
+    }
+}
@@ -0,0 +1,255 @@
+use crate::key_schedule::KeySchedule;
+use crate::cbc::xor_in_place;
+use crate::{AESError, AESteve, Padding, PaddingScheme};
+use rayon::prelude::*;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Key expansions timed per call; a single expansion is too short to time reliably.
+const EXPANSIONS: u32 = 1024;
+
+/// Measures how long one AES-128 key expansion takes on this host, in nanoseconds.
+///
+/// Averages over a batch of expansions. The result depends on the CPU, its current
+/// frequency and load, and the build profile, so measure on the deployment host (in a
+/// release build) rather than hard-coding values from elsewhere.
+///
+/// # Returns
+///
+/// * `u64` - The mean duration of a key expansion in nanoseconds.
+pub fn expand_key_nanos() -> u64 {
+    let start = Instant::now();
+    for i in 0..EXPANSIONS {
+        let mut key = [0x5Au8; 16];
+        key[..4].copy_from_slice(&i.to_le_bytes());
+        let _ = black_box(KeySchedule::expand(black_box(&key), 4));
+    }
+    (start.elapsed().as_nanos() / u128::from(EXPANSIONS)) as u64
+}
+
+impl AESteve {
+    /// Decrypts one block with the FIPS-197 equivalent inverse cipher.
+    ///
+    /// Same result as `decrypt_block`, but each round runs InvSubBytes, InvShiftRows,
+    /// InvMixColumns and AddRoundKey in the encryption order, using the inner round keys
+    /// with InvMixColumns already applied (`decryption_keys`).
+    fn decrypt_block_equivalent(&self, block: [[u8; 4]; 4], decryption_keys: &[[[u8; 4]; 4]]) -> [[u8; 4]; 4] {
+        let rounds = decryption_keys.len() - 1;
+        let mut new_block = Self::add_round_key(decryption_keys[rounds], block);
+        for round in (1..rounds).rev() {
+            new_block = self.inv_sub_bytes(new_block);
+            new_block = Self::inv_shift_rows(new_block);
+            new_block = Self::inv_mix_columns(new_block);
+            new_block = Self::add_round_key(decryption_keys[round], new_block);
+        }
+        new_block = self.inv_sub_bytes(new_block);
+        new_block = Self::inv_shift_rows(new_block);
+        Self::add_round_key(decryption_keys[0], new_block)
+    }
+
+    /// Decrypts CBC ciphertext one block at a time on the calling thread.
+    ///
+    /// The reference [`AESteve::decrypt_cbc`] is measured against: same result, without
+    /// the parallel block decryption.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The raw ciphertext.
+    /// * `iv` - The initialization vector the ciphertext was produced with.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`AESteve::decrypt_cbc`].
+    pub fn decrypt_cbc_sequential(&self, ciphertext: &[u8], iv: &[u8; 16]) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < Self::min_ciphertext_len() {
+            return Err(AESError::CiphertextTooShort);
+        }
+        if !ciphertext.len().is_multiple_of(16) {
+            return Err(AESError::InvalidCiphertextLength);
+        }
+
+        let mut plaintext = Vec::with_capacity(ciphertext.len());
+        let mut previous: &[u8] = iv;
+        for chunk in ciphertext.chunks_exact(16) {
+            let mut block = self.decrypt_block_bytes(chunk.try_into().unwrap());
+            xor_in_place(&mut block, previous);
+            plaintext.extend_from_slice(&block);
+            previous = chunk;
+        }
+        Padding::Pkcs7.depad(plaintext)
+    }
+
+    /// Encrypts whole blocks through the four-block path, split into rayon tasks exactly
+    /// as `encrypt_with_padding` splits them.
+    ///
+    /// Together with [`AESteve::encrypt_blocks_single`] this isolates the cost of the
+    /// block function: both copy `blocks` once and use the same task sizes.
+    ///
+    /// # Arguments
+    ///
+    /// * `blocks` - The bytes to encrypt, a whole number of blocks.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext, as long as `blocks`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blocks` is not a whole number of 16-byte blocks.
+    pub fn encrypt_blocks_grouped(&self, blocks: &[u8]) -> Vec<u8> {
+        assert!(blocks.len().is_multiple_of(16), "blocks must be a whole number of blocks");
+        self.encrypt_blocks(blocks.to_vec())
+    }
+
+    /// Encrypts whole blocks like [`AESteve::encrypt_blocks_grouped`], but with one
+    /// `encrypt_block_bytes` call per block: the baseline for the four-block path.
+    ///
+    /// # Arguments
+    ///
+    /// * `blocks` - The bytes to encrypt, a whole number of blocks.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext, as long as `blocks`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blocks` is not a whole number of 16-byte blocks.
+    pub fn encrypt_blocks_single(&self, blocks: &[u8]) -> Vec<u8> {
+        assert!(blocks.len().is_multiple_of(16), "blocks must be a whole number of blocks");
+        let mut buffer = blocks.to_vec();
+        let task_len = self.blocks_per_task(buffer.len() / 16) * 16;
+        buffer.par_chunks_mut(task_len).for_each(|task| {
+            for chunk in task.chunks_exact_mut(16) {
+                let block: &mut [u8; 16] = chunk.try_into().unwrap();
+                *block = self.encrypt_block_bytes(block);
+            }
+        });
+        buffer
+    }
+
+    /// Encrypts many messages like [`AESteve::encrypt_batch`], but without sizing each
+    /// working buffer and encoded string up front: the baseline for its pre-reservation.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - The messages to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The same output as `encrypt_batch`.
+    pub fn encrypt_batch_unreserved(&self, messages: &[&str]) -> Vec<String> {
+        self.encrypt_batch_sized(messages, false)
+    }
+
+    /// The round keys for the equivalent inverse cipher.
+    fn equivalent_decryption_keys(&self) -> Vec<[[u8; 4]; 4]> {
+        let mut decryption_keys = self.schedule.to_vec();
+        let rounds = self.schedule.rounds;
+        for key in &mut decryption_keys[1..rounds] {
+            *key = Self::inv_mix_columns(*key);
+        }
+        decryption_keys
+    }
+}
+
+fn megabytes_per_second(bytes: usize, elapsed: Duration) -> f64 {
+    bytes as f64 / elapsed.as_secs_f64().max(f64::MIN_POSITIVE) / 1e6
+}
+
+/// Measures single-threaded decryption throughput of the standard inverse cipher and the
+/// FIPS-197 equivalent inverse cipher on this host, in MB/s.
+///
+/// Decryption always uses the standard inverse cipher; this measures whether switching
+/// to the equivalent inverse would pay off for a workload. Both paths decrypt the same
+/// `bytes` of ciphertext (rounded up to whole blocks) block by block on the calling
+/// thread. As with [`expand_key_nanos`], measure in a release build on the deployment host.
+///
+/// # Arguments
+///
+/// * `bytes` - How much ciphertext each path decrypts.
+///
+/// # Returns
+///
+/// * `(f64, f64)` - The standard and equivalent inverse throughput in MB/s.
+pub fn compare_decrypt_paths(bytes: usize) -> (f64, f64) {
+    let cipher = AESteve::new(&[0x5Au8; 16]).unwrap();
+    let blocks: Vec<[[u8; 4]; 4]> = (0..bytes.div_ceil(16).max(1) as u32)
+        .map(|i| {
+            let mut block = [0u8; 16];
+            block[..4].copy_from_slice(&i.to_le_bytes());
+            crate::bytes_to_block(&block)
+        })
+        .collect();
+    let measured = blocks.len() * 16;
+
+    let start = Instant::now();
+    for &block in &blocks {
+        black_box(cipher.decrypt_block(black_box(block)));
+    }
+    let standard = megabytes_per_second(measured, start.elapsed());
+
+    let start = Instant::now();
+    let decryption_keys = cipher.equivalent_decryption_keys();
+    for &block in &blocks {
+        black_box(cipher.decrypt_block_equivalent(black_box(block), &decryption_keys));
+    }
+    let equivalent = megabytes_per_second(measured, start.elapsed());
+
+    (standard, equivalent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_key_nanos() {
+        assert!(expand_key_nanos() < 1_000_000);
+    }
+
+    #[test]
+    fn test_equivalent_inverse_matches_standard() {
+        // FIPS-197, appendix C.1.
+        let key: Vec<u8> = (0u8..16).collect();
+        let aesteve = AESteve::new(&key).unwrap();
+        let ciphertext = crate::bytes_to_block(&[
+            0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a,
+        ]);
+        let decryption_keys = aesteve.equivalent_decryption_keys();
+        assert_eq!(
+            crate::block_to_bytes(&aesteve.decrypt_block_equivalent(ciphertext, &decryption_keys)),
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff]
+        );
+        assert_eq!(aesteve.decrypt_block_equivalent(ciphertext, &decryption_keys), aesteve.decrypt_block(ciphertext));
+    }
+
+    #[test]
+    fn test_decrypt_cbc_sequential_matches_parallel() {
+        let aesteve = AESteve::new(&[0x5Au8; 16]).unwrap();
+        let data = vec![0xC3u8; 5000];
+        let ciphertext = aesteve.encrypt_cbc(&data, &[7u8; 16]);
+        assert_eq!(aesteve.decrypt_cbc_sequential(&ciphertext, &[7u8; 16]).unwrap(), data);
+        assert!(matches!(aesteve.decrypt_cbc_sequential(&ciphertext[..20], &[7u8; 16]), Err(AESError::InvalidCiphertextLength)));
+    }
+
+    #[test]
+    fn test_grouped_and_single_blocks_match() {
+        let aesteve = AESteve::new(&[0x5Au8; 16]).unwrap();
+        let blocks: Vec<u8> = (0..16 * 37).map(|i| i as u8).collect();
+        let expected = aesteve.encrypt_with_padding(&blocks, &Padding::Iso7816);
+        assert_eq!(aesteve.encrypt_blocks_grouped(&blocks), expected[..blocks.len()]);
+        assert_eq!(aesteve.encrypt_blocks_single(&blocks), expected[..blocks.len()]);
+    }
+
+    #[test]
+    fn test_compare_decrypt_paths() {
+        let (standard, equivalent) = compare_decrypt_paths(4096);
+        assert!(standard > 0.0 && standard.is_finite());
+        assert!(equivalent > 0.0 && equivalent.is_finite());
+    }
+}
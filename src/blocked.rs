@@ -0,0 +1,50 @@
+use crate::{AESteve, Padding, PaddingScheme};
+use rayon::prelude::*;
+
+/// Bytes handed to each rayon task, sized to stay resident in a typical L2 cache.
+const TILE_SIZE: usize = 64 * 1024;
+
+impl AESteve {
+    /// Encrypts the given bytes in ECB mode, processing the buffer in cache-sized tiles.
+    ///
+    /// Produces the same output as `encrypt_with_padding(data, &Padding::Iso7816)`, but each
+    /// rayon task encrypts a contiguous tile in place instead of a single block. This is an
+    /// experimental layout kept behind the `experimental` feature for benchmarking.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext.
+    pub fn encrypt_bytes_blocked(&self, data: &[u8]) -> Vec<u8> {
+        let mut buffer = data.to_vec();
+        Padding::Iso7816.pad(&mut buffer);
+
+        buffer.par_chunks_mut(TILE_SIZE).for_each(|tile| {
+            for chunk in tile.chunks_exact_mut(16) {
+                let block: &mut [u8; 16] = chunk.try_into().unwrap();
+                *block = self.encrypt_block_bytes(block);
+            }
+        });
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocked_matches_parallel_map() {
+        let aesteve = AESteve::new(&[7u8; 16]).unwrap();
+        for len in [0, 15, 16, 1000, TILE_SIZE - 1, TILE_SIZE * 3 + 5] {
+            let data: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            assert_eq!(
+                aesteve.encrypt_bytes_blocked(&data),
+                aesteve.encrypt_with_padding(&data, &Padding::Iso7816)
+            );
+        }
+    }
+}
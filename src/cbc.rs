@@ -0,0 +1,301 @@
+use crate::encoding::Encoding;
+use crate::{random_array, AESError, AESteve, Padding, PaddingScheme};
+use rayon::prelude::*;
+
+pub(crate) fn xor_in_place(block: &mut [u8; 16], other: &[u8]) {
+    for (byte, &o) in block.iter_mut().zip(other) {
+        *byte ^= o;
+    }
+}
+
+impl AESteve {
+    /// Encrypts the given bytes in CBC mode with PKCS#7 padding.
+    ///
+    /// Each plaintext block is XORed with the previous ciphertext block (the IV for the
+    /// first) before encryption, so unlike ECB, repeated plaintext blocks do not show up
+    /// as repeated ciphertext blocks. Encryption is inherently sequential.
+    ///
+    /// The output is byte-for-byte what `openssl enc -aes-128-cbc -K <key> -iv <iv>`
+    /// writes for the same input (`-aes-192-cbc` and `-aes-256-cbc` for longer keys): the
+    /// ciphertext only, with no salt header and no IV prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted.
+    /// * `iv` - The 16-byte initialization vector. Must be unpredictable for each message.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext.
+    pub fn encrypt_cbc(&self, data: &[u8], iv: &[u8; 16]) -> Vec<u8> {
+        let mut buffer = data.to_vec();
+        Padding::Pkcs7.pad(&mut buffer);
+
+        let mut previous = *iv;
+        for chunk in buffer.chunks_exact_mut(16) {
+            let block: &mut [u8; 16] = chunk.try_into().unwrap();
+            xor_in_place(block, &previous);
+            *block = self.encrypt_block_bytes(block);
+            previous = *block;
+        }
+        buffer
+    }
+
+    /// Decrypts CBC ciphertext with PKCS#7 padding, such as the output of
+    /// `openssl enc -aes-128-cbc -K <key> -iv <iv>`.
+    ///
+    /// Ciphertext produced with a password (`-k`/`-pass`) carries a `Salted__` header and
+    /// a derived key; it is not supported, pass the key and IV explicitly instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The raw ciphertext.
+    /// * `iv` - The initialization vector the ciphertext was produced with.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::CiphertextTooShort` if `ciphertext` is shorter than one block,
+    /// `AESError::InvalidCiphertextLength` if it is not a multiple of 16 bytes, or
+    /// `AESError::InvalidPadding` if the decrypted bytes are not PKCS#7 padded.
+    pub fn decrypt_cbc(&self, ciphertext: &[u8], iv: &[u8; 16]) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < Self::min_ciphertext_len() {
+            return Err(AESError::CiphertextTooShort);
+        }
+        if !ciphertext.len().is_multiple_of(16) {
+            return Err(AESError::InvalidCiphertextLength);
+        }
+
+        // Unlike encryption, every block only needs its own ciphertext and the one before
+        // it, both known up front, so blocks are decrypted in parallel like ECB.
+        let mut plaintext = ciphertext.to_vec();
+        let task_len = self.blocks_per_task(ciphertext.len() / 16) * 16;
+        plaintext
+            .par_chunks_mut(task_len)
+            .enumerate()
+            .for_each(|(task, run)| {
+                for (i, chunk) in run.chunks_exact_mut(16).enumerate() {
+                    let offset = task * task_len + i * 16;
+                    let previous = if offset == 0 { &iv[..] } else { &ciphertext[offset - 16..offset] };
+                    let block: &mut [u8; 16] = chunk.try_into().unwrap();
+                    *block = self.decrypt_block_bytes(block);
+                    xor_in_place(block, previous);
+                }
+            });
+        Padding::Pkcs7.depad(plaintext)
+    }
+
+    /// Decrypts the Base64 output of `openssl enc -aes-128-cbc -a -K <key> -iv <iv>`.
+    ///
+    /// OpenSSL wraps `-a` output at 64 columns unless `-A` is given; line breaks and
+    /// other ASCII whitespace are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The Base64-encoded ciphertext.
+    /// * `iv` - The initialization vector the ciphertext was produced with.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::CiphertextTooLarge` if `ciphertext` would decode to more than the
+    /// limit set with `with_max_ciphertext_len`, `AESError::InvalidBase64` if it is not
+    /// valid Base64, or any error returned by [`AESteve::decrypt_cbc`].
+    pub fn decrypt_cbc_base64(&self, ciphertext: &str, iv: &[u8; 16]) -> Result<Vec<u8>, AESError> {
+        let unwrapped: String = ciphertext.split_ascii_whitespace().collect();
+        self.check_ciphertext_len(Encoding::decoded_len(&unwrapped))?;
+        self.decrypt_cbc(&Encoding::Base64.decode(&unwrapped)?, iv)
+    }
+
+    /// Derives a CBC IV from a message counter by encrypting the counter block, as in
+    /// NIST SP 800-38A, appendix C.
+    ///
+    /// The IV is `E_k(0^64 ‖ counter)` with the counter big-endian: deterministic, so no
+    /// random number generator is needed, yet unpredictable to anyone without the key.
+    /// The counter must never repeat for a given key, across restarts included; a
+    /// repeated counter repeats the IV and reveals whether two messages share a prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `counter` - The message counter. Use each value at most once per key.
+    ///
+    /// # Returns
+    ///
+    /// * `[u8; 16]` - The IV to pass to [`AESteve::encrypt_cbc`].
+    pub fn deterministic_iv(&self, counter: u64) -> [u8; 16] {
+        let mut block = [0u8; 16];
+        block[8..].copy_from_slice(&counter.to_be_bytes());
+        self.encrypt_block_bytes(&block)
+    }
+
+    /// Encrypts `message` in CBC mode under a fresh random IV, returning the IV and the
+    /// ciphertext as separate strings.
+    ///
+    /// Matches JSON APIs with separate `iv` and `data` fields. Both strings use the
+    /// configured encoding (Base64 by default).
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The bytes to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `(String, String)` - The encoded IV and the encoded ciphertext.
+    pub fn encrypt_cbc_parts(&self, message: &[u8]) -> (String, String) {
+        let iv: [u8; 16] = random_array();
+        let ciphertext = self.encrypt_cbc(message, &iv);
+        (self.encoding.encode(&iv), self.encoding.encode(&ciphertext))
+    }
+
+    /// Decrypts the IV and ciphertext strings produced by [`AESteve::encrypt_cbc_parts`].
+    ///
+    /// # Arguments
+    ///
+    /// * `iv` - The encoded IV.
+    /// * `data` - The encoded ciphertext.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::CiphertextTooLarge` if either string would decode to more than
+    /// the limit set with `with_max_ciphertext_len`, `AESError::InvalidBase64` if either
+    /// does not decode, `AESError::InvalidDataLength` if the IV is not 16 bytes, or any
+    /// error returned by [`AESteve::decrypt_cbc`].
+    pub fn decrypt_cbc_parts(&self, iv: &str, data: &str) -> Result<Vec<u8>, AESError> {
+        let iv: [u8; 16] = self.decode_ciphertext(iv)?.try_into().map_err(|_| AESError::InvalidDataLength)?;
+        self.decrypt_cbc(&self.decode_ciphertext(data)?, &iv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // printf 'OpenSSL interop: AES-128-CBC with PKCS#7.' | openssl enc -aes-128-cbc \
+    //     -K 2b7e151628aed2a6abf7158809cf4f3c -iv 000102030405060708090a0b0c0d0e0f
+    const KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6,
+        0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+    ];
+    const IV: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+        0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+    ];
+    const PLAINTEXT: &[u8] = b"OpenSSL interop: AES-128-CBC with PKCS#7.";
+    const CIPHERTEXT: [u8; 48] = [
+        0xdd, 0x46, 0x27, 0xfa, 0x42, 0x46, 0x7b, 0xca, 0x77, 0x15, 0xfa, 0x9e,
+        0x41, 0xc9, 0x54, 0x11, 0x28, 0x9f, 0xdb, 0xd4, 0xd6, 0x3c, 0x10, 0x1d,
+        0x19, 0x6a, 0xec, 0x2f, 0xf8, 0xd2, 0xd0, 0x65, 0xdf, 0xe7, 0xad, 0xae,
+        0x82, 0x9b, 0x92, 0x7c, 0xe0, 0x55, 0x5f, 0xd0, 0xf0, 0x27, 0xca, 0xf4,
+    ];
+
+    #[test]
+    fn test_openssl_cbc_vector() {
+        let aesteve = AESteve::new(&KEY).unwrap();
+        assert_eq!(aesteve.encrypt_cbc(PLAINTEXT, &IV), CIPHERTEXT);
+        assert_eq!(aesteve.decrypt_cbc(&CIPHERTEXT, &IV).unwrap(), PLAINTEXT);
+    }
+
+    #[test]
+    fn test_cbc_hides_repeated_blocks() {
+        let aesteve = AESteve::new(&KEY).unwrap();
+        let ciphertext = aesteve.encrypt_cbc(&[0x41; 64], &IV);
+        let blocks: Vec<&[u8]> = ciphertext.chunks(16).collect();
+        assert!(blocks.iter().enumerate().all(|(i, a)| blocks[i + 1..].iter().all(|b| a != b)));
+        assert_eq!(aesteve.encrypt_bytes(&[0x41; 64])[..16], aesteve.encrypt_bytes(&[0x41; 64])[16..32]);
+    }
+
+    #[test]
+    fn test_cbc_aes256_sp800_38a() {
+        // NIST SP 800-38A, F.2.5 (CBC-AES256.Encrypt), first block.
+        let key = [
+            0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77, 0x81,
+            0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14, 0xdf, 0xf4,
+        ];
+        let plaintext = [0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a];
+        let aesteve = AESteve::new(&key).unwrap();
+        let ciphertext = aesteve.encrypt_cbc(&plaintext, &IV);
+        assert_eq!(
+            ciphertext[..16],
+            [0xf5, 0x8c, 0x4c, 0x04, 0xd6, 0xe5, 0xf1, 0xba, 0x77, 0x9e, 0xab, 0xfb, 0x5f, 0x7b, 0xfb, 0xd6]
+        );
+        assert_eq!(aesteve.decrypt_cbc(&ciphertext, &IV).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_openssl_cbc_base64() {
+        // Same command with `-a`.
+        let output = "3UYn+kJGe8p3FfqeQclUESif29TWPBAdGWrsL/jS0GXf562ugpuSfOBVX9DwJ8r0\n";
+        let aesteve = AESteve::new(&KEY).unwrap();
+        assert_eq!(aesteve.decrypt_cbc_base64(output, &IV).unwrap(), PLAINTEXT);
+    }
+
+    #[test]
+    fn test_cbc_invalid_length() {
+        let aesteve = AESteve::new(&KEY).unwrap();
+        assert!(matches!(aesteve.decrypt_cbc(&CIPHERTEXT[..8], &IV), Err(AESError::CiphertextTooShort)));
+        assert!(matches!(aesteve.decrypt_cbc(&CIPHERTEXT[..20], &IV), Err(AESError::InvalidCiphertextLength)));
+    }
+
+    #[test]
+    fn test_parallel_decrypt_matches_sequential() {
+        let aesteve = AESteve::new(&KEY).unwrap().with_chunk_size(4);
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 253) as u8).collect();
+        let ciphertext = aesteve.encrypt_cbc(&data, &IV);
+
+        let mut sequential = Vec::with_capacity(ciphertext.len());
+        let mut previous = IV;
+        for chunk in ciphertext.chunks_exact(16) {
+            let mut block = aesteve.decrypt_block_bytes(chunk.try_into().unwrap());
+            xor_in_place(&mut block, &previous);
+            sequential.extend_from_slice(&block);
+            previous = chunk.try_into().unwrap();
+        }
+        assert_eq!(aesteve.decrypt_cbc(&ciphertext, &IV).unwrap(), Padding::Pkcs7.depad(sequential).unwrap());
+        assert_eq!(AESteve::new(&KEY).unwrap().decrypt_cbc(&ciphertext, &IV).unwrap(), data);
+        assert_eq!(aesteve.with_chunk_size(usize::MAX).decrypt_cbc(&ciphertext, &IV).unwrap(), data);
+    }
+
+    #[test]
+    fn test_deterministic_iv() {
+        let aesteve = AESteve::new(&KEY).unwrap();
+        assert_eq!(aesteve.deterministic_iv(7), aesteve.deterministic_iv(7));
+        assert_ne!(aesteve.deterministic_iv(7), aesteve.deterministic_iv(8));
+        assert_ne!(aesteve.deterministic_iv(7), AESteve::new(&IV).unwrap().deterministic_iv(7));
+
+        let iv = aesteve.deterministic_iv(0);
+        assert_eq!(iv, aesteve.encrypt_array([0u8; 16]));
+        assert_eq!(aesteve.decrypt_cbc(&aesteve.encrypt_cbc(PLAINTEXT, &iv), &iv).unwrap(), PLAINTEXT);
+    }
+
+    #[test]
+    fn test_cbc_parts_round_trip() {
+        let aesteve = AESteve::new(&KEY).unwrap();
+        let (iv, data) = aesteve.encrypt_cbc_parts(b"{\"user\":42}");
+        assert_eq!(aesteve.decrypt_cbc_parts(&iv, &data).unwrap(), b"{\"user\":42}");
+        assert_ne!(aesteve.encrypt_cbc_parts(b"{\"user\":42}").0, iv);
+        assert!(matches!(aesteve.decrypt_cbc_parts("AAAA", &data), Err(AESError::InvalidDataLength)));
+    }
+
+    #[test]
+    fn test_cbc_base64_max_len() {
+        let output = "3UYn+kJGe8p3FfqeQclUESif29TWPBAdGWrsL/jS0GXf562ugpuSfOBVX9DwJ8r0\n";
+        let aesteve = AESteve::new(&KEY).unwrap().with_max_ciphertext_len(32);
+        assert!(matches!(aesteve.decrypt_cbc_base64(output, &IV), Err(AESError::CiphertextTooLarge)));
+        assert_eq!(aesteve.clone().with_max_ciphertext_len(48).decrypt_cbc_base64(output, &IV).unwrap(), PLAINTEXT);
+
+        let (iv, data) = aesteve.encrypt_cbc_parts(b"short");
+        assert_eq!(aesteve.decrypt_cbc_parts(&iv, &data).unwrap(), b"short");
+        let oversized_iv = aesteve.encoding.encode(&[0u8; 64]);
+        assert!(matches!(aesteve.decrypt_cbc_parts(&oversized_iv, &data), Err(AESError::CiphertextTooLarge)));
+    }
+}
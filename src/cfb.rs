@@ -0,0 +1,109 @@
+use crate::AESteve;
+use rayon::prelude::*;
+
+fn xor_into(out: &mut [u8], keystream: &[u8; 16]) {
+    for (byte, key) in out.iter_mut().zip(keystream) {
+        *byte ^= key;
+    }
+}
+
+impl AESteve {
+    /// Encrypts `data` in CFB mode with 128-bit feedback (CFB128), without padding.
+    ///
+    /// Each ciphertext block is the plaintext block XORed with the encryption of the
+    /// previous ciphertext block (the IV for the first). A final partial block uses only
+    /// as many keystream bytes as it needs, so the output is as long as `data`.
+    /// Encryption is sequential, since each block needs the previous ciphertext.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted.
+    /// * `iv` - The 16-byte initialization vector. Must be unpredictable for each message.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The ciphertext.
+    pub fn encrypt_cfb(&self, data: &[u8], iv: &[u8; 16]) -> Vec<u8> {
+        let mut output = data.to_vec();
+        let mut previous = *iv;
+        for chunk in output.chunks_mut(16) {
+            xor_into(chunk, &self.encrypt_block_bytes(&previous));
+            if let Ok(block) = <[u8; 16]>::try_from(&*chunk) {
+                previous = block;
+            }
+        }
+        output
+    }
+
+    /// Decrypts CFB128 ciphertext produced by [`AESteve::encrypt_cfb`].
+    ///
+    /// Like encryption, this only ever runs the cipher forwards: the keystream for each
+    /// block is the *encryption* of the previous ciphertext block, never a decryption.
+    /// All ciphertext blocks are known up front, so blocks are processed in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The ciphertext.
+    /// * `iv` - The initialization vector the ciphertext was produced with.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The plaintext.
+    pub fn decrypt_cfb(&self, ciphertext: &[u8], iv: &[u8; 16]) -> Vec<u8> {
+        let mut output = ciphertext.to_vec();
+        output.par_chunks_mut(16).enumerate().for_each(|(i, chunk)| {
+            let previous: &[u8; 16] = if i == 0 { iv } else { ciphertext[(i - 1) * 16..i * 16].try_into().unwrap() };
+            xor_into(chunk, &self.encrypt_block_bytes(previous));
+        });
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // NIST SP 800-38A, F.3.13 (CFB128-AES128.Encrypt), first two blocks.
+    const KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+    ];
+    const IV: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+    ];
+    const PLAINTEXT: [u8; 32] = [
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+        0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+    ];
+    const CIPHERTEXT: [u8; 32] = [
+        0x3b, 0x3f, 0xd9, 0x2e, 0xb7, 0x2d, 0xad, 0x20, 0x33, 0x34, 0x49, 0xf8, 0xe8, 0x3c, 0xfb, 0x4a,
+        0xc8, 0xa6, 0x45, 0x37, 0xa0, 0xb3, 0xa9, 0x3f, 0xcd, 0xe3, 0xcd, 0xad, 0x9f, 0x1c, 0xe5, 0x8b,
+    ];
+
+    #[test]
+    fn test_cfb_sp800_38a() {
+        let aesteve = AESteve::new(&KEY).unwrap();
+        assert_eq!(aesteve.encrypt_cfb(&PLAINTEXT, &IV), CIPHERTEXT);
+        assert_eq!(aesteve.decrypt_cfb(&CIPHERTEXT, &IV), PLAINTEXT);
+    }
+
+    #[test]
+    fn test_cfb_partial_block() {
+        let aesteve = AESteve::new(&KEY).unwrap();
+        assert_eq!(aesteve.encrypt_cfb(&PLAINTEXT[..21], &IV), CIPHERTEXT[..21]);
+        assert_eq!(aesteve.decrypt_cfb(&CIPHERTEXT[..21], &IV), PLAINTEXT[..21]);
+        assert!(aesteve.encrypt_cfb(&[], &IV).is_empty());
+    }
+
+    #[test]
+    fn test_cfb_decrypt_uses_forward_cipher() {
+        // Rebuild the plaintext with `encrypt_array` alone: CFB decryption is the forward
+        // cipher applied to the previous ciphertext block, not `decrypt_array`.
+        let aesteve = AESteve::new(&KEY).unwrap();
+        let first = aesteve.encrypt_array(IV);
+        let second = aesteve.encrypt_array(CIPHERTEXT[..16].try_into().unwrap());
+        let keystream: Vec<u8> = first.into_iter().chain(second).collect();
+        let rebuilt: Vec<u8> = CIPHERTEXT.iter().zip(&keystream).map(|(c, k)| c ^ k).collect();
+        assert_eq!(rebuilt, PLAINTEXT);
+        assert_ne!(aesteve.decrypt_array(CIPHERTEXT[..16].try_into().unwrap()), first);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{random_array, AESError, AESteve};
+use rayon::prelude::*;
+
+/// Domain-separation label for the chunk nonces.
+const LABEL: &[u8] = b"AESteve chunked-chain v1";
+const NONCE_KEY_LABEL: &[u8] = b"AESteve chunked-chain v1 nonce key";
+const CTR_KEY_LABEL: &[u8] = b"AESteve chunked-chain v1 ctr key";
+const IV_LEN: usize = 16;
+
+impl AESteve {
+    /// The nonce-PRF and CTR subkeys, so the master key is never used as both a MAC key
+    /// and a keystream key.
+    fn chain_keys(&self) -> (AESteve, AESteve) {
+        (self.derive_subkey(NONCE_KEY_LABEL), self.derive_subkey(CTR_KEY_LABEL))
+    }
+
+    /// Derives the CTR nonce for the chunk after the one encrypted under `nonce`, keyed by
+    /// the nonce subkey.
+    fn next_chunk_nonce(&self, nonce: &[u8; 16], ciphertext_chunk: &[u8]) -> [u8; 16] {
+        let mut input = Vec::with_capacity(16 + ciphertext_chunk.len());
+        input.extend_from_slice(nonce);
+        input.extend_from_slice(ciphertext_chunk);
+        self.prf(LABEL, &input)
+    }
+
+    /// Encrypts `data` in CTR mode chunk by chunk, chaining each chunk's nonce to all the
+    /// ciphertext before it.
+    ///
+    /// The output is `iv ‖ chunk_0 ‖ chunk_1 ‖ …` with a random 16-byte `iv` and no
+    /// padding. Chunk 0 is CTR-encrypted (as [`AESteve::encrypt_ctr`]) under the
+    /// nonce `prf(label, iv)`, and chunk `i + 1` under `prf(label, nonce_i ‖ chunk_i)`,
+    /// where `prf` is [`AESteve::prf`] (AES-CMAC) over ciphertext. The nonce PRF and the
+    /// CTR keystream run under two subkeys derived from this instance's key. Blocks within
+    /// a chunk are encrypted in parallel; chunks are encrypted one after another.
+    ///
+    /// Security properties:
+    /// * Confidentiality is that of CTR under a random IV, as long as no 128-bit counter
+    ///   value repeats, which random nonces make negligible for realistic data sizes.
+    /// * Chunks are bound to their position: moving, dropping or inserting a chunk changes
+    ///   the nonces of every chunk after it, so they decrypt to garbage.
+    /// * There is NO authentication. Decryption never fails on tampered data; it returns
+    ///   garbage instead, and bit flips within the last chunk pass through undetected.
+    ///   Add a MAC over the output if integrity matters.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted.
+    /// * `chunk_size` - The chunk length in bytes; 0 is treated as 1.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The IV followed by the encrypted chunks, `data.len() + 16` bytes in all.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system's random number generator is unavailable.
+    pub fn encrypt_chunked_chain(&self, data: &[u8], chunk_size: usize) -> Vec<u8> {
+        let iv: [u8; IV_LEN] = random_array();
+        let mut output = Vec::with_capacity(IV_LEN + data.len());
+        output.extend_from_slice(&iv);
+
+        let (nonce_key, ctr_key) = self.chain_keys();
+        let mut nonce = nonce_key.prf(LABEL, &iv);
+        for chunk in data.chunks(chunk_size.max(1)) {
+            let encrypted = ctr_key.encrypt_ctr(chunk, &nonce);
+            nonce = nonce_key.next_chunk_nonce(&nonce, &encrypted);
+            output.extend_from_slice(&encrypted);
+        }
+        output
+    }
+
+    /// Decrypts output of [`AESteve::encrypt_chunked_chain`].
+    ///
+    /// Every nonce depends only on ciphertext, so they are derived up front and the chunks
+    /// are then decrypted in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The IV followed by the encrypted chunks.
+    /// * `chunk_size` - The chunk length the data was encrypted with.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted bytes. Not authenticated; see `encrypt_chunked_chain`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::CiphertextTooShort` if `ciphertext` is shorter than the IV.
+    pub fn decrypt_chunked_chain(&self, ciphertext: &[u8], chunk_size: usize) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < IV_LEN {
+            return Err(AESError::CiphertextTooShort);
+        }
+        let (iv, body) = ciphertext.split_at(IV_LEN);
+        let chunks: Vec<&[u8]> = body.chunks(chunk_size.max(1)).collect();
+
+        let (nonce_key, ctr_key) = self.chain_keys();
+        let mut nonces = Vec::with_capacity(chunks.len());
+        let mut nonce = nonce_key.prf(LABEL, iv);
+        for chunk in &chunks {
+            nonces.push(nonce);
+            nonce = nonce_key.next_chunk_nonce(&nonce, chunk);
+        }
+
+        let plaintext: Vec<Vec<u8>> = chunks
+            .par_iter()
+            .zip(&nonces)
+            .map(|(chunk, nonce)| ctr_key.encrypt_ctr(chunk, nonce))
+            .collect();
+        Ok(plaintext.concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunked_chain_round_trip() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+        for chunk_size in [0, 1, 16, 100, 1000, 4096] {
+            let ciphertext = aesteve.encrypt_chunked_chain(&data, chunk_size);
+            assert_eq!(ciphertext.len(), data.len() + IV_LEN);
+            assert_eq!(aesteve.decrypt_chunked_chain(&ciphertext, chunk_size).unwrap(), data);
+        }
+        assert_ne!(aesteve.encrypt_chunked_chain(&data, 100), aesteve.encrypt_chunked_chain(&data, 100));
+        assert!(aesteve.decrypt_chunked_chain(&[0u8; IV_LEN], 100).unwrap().is_empty());
+        assert!(matches!(aesteve.decrypt_chunked_chain(&[0u8; 8], 100), Err(AESError::CiphertextTooShort)));
+    }
+
+    #[test]
+    fn test_chunked_chain_separates_keys() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let data = [0x44u8; 64];
+        let ciphertext = aesteve.encrypt_chunked_chain(&data, 32);
+        let (iv, body) = ciphertext.split_at(IV_LEN);
+
+        let (nonce_key, ctr_key) = aesteve.chain_keys();
+        let nonce = nonce_key.prf(LABEL, iv);
+        assert_ne!(nonce, aesteve.prf(LABEL, iv));
+        assert_eq!(body[..32], ctr_key.encrypt_ctr(&data[..32], &nonce));
+        assert_ne!(body[..32], aesteve.encrypt_ctr(&data[..32], &nonce));
+    }
+
+    #[test]
+    fn test_chunked_chain_reordering_breaks_decryption() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let data = [[0x11u8; 32], [0x22; 32], [0x33; 32]].concat();
+        let ciphertext = aesteve.encrypt_chunked_chain(&data, 32);
+
+        let (iv, body) = ciphertext.split_at(IV_LEN);
+        let swapped = [iv, &body[32..64], &body[..32], &body[64..]].concat();
+        let decrypted = aesteve.decrypt_chunked_chain(&swapped, 32).unwrap();
+        for (i, original) in data.chunks(32).enumerate() {
+            assert_ne!(&decrypted[i * 32..(i + 1) * 32], original);
+        }
+        for i in 0..3 {
+            assert_ne!(&decrypted[i * 32..(i + 1) * 32], &data[32..64]);
+            assert_ne!(&decrypted[i * 32..(i + 1) * 32], &data[..32]);
+        }
+    }
+}
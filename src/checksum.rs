@@ -0,0 +1,74 @@
+use crate::{AESError, AESteve};
+
+/// The reflected CRC-32 (IEEE 802.3) polynomial.
+const CRC32_POLY: u32 = 0xEDB8_8320;
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (CRC32_POLY & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+impl AESteve {
+    /// Enables a CRC-32 of the plaintext inside the encrypted region.
+    ///
+    /// When enabled, `encrypt_bytes` appends a big-endian CRC-32 of the plaintext before
+    /// padding, and `decrypt_bytes` verifies and strips it. This catches accidental
+    /// corruption cheaply. It is NOT a substitute for a MAC: an active attacker can alter
+    /// ciphertext and fix up the checksum. Defaults to disabled.
+    pub fn with_checksum(mut self, enabled: bool) -> Self {
+        self.checksum = enabled;
+        self
+    }
+
+    pub(crate) fn append_checksum(&self, message: &mut Vec<u8>) {
+        if self.checksum {
+            let crc = crc32(message);
+            message.extend_from_slice(&crc.to_be_bytes());
+        }
+    }
+
+    /// Verifies the trailing checksum, returning the length of the message without it.
+    pub(crate) fn verify_checksum(&self, message: &[u8]) -> Result<usize, AESError> {
+        if !self.checksum {
+            return Ok(message.len());
+        }
+        let payload_len = message.len().checked_sub(4).ok_or(AESError::ChecksumMismatch)?;
+        let (payload, crc) = message.split_at(payload_len);
+        if crc32(payload).to_be_bytes() != crc {
+            return Err(AESError::ChecksumMismatch);
+        }
+        Ok(payload_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_checksum_round_trip() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap().with_checksum(true);
+        let message = String::from("low-value telemetry record");
+        let encrypted_message = aesteve.encrypt_ecb(message.clone()).unwrap();
+        assert_eq!(aesteve.decrypt_ecb(encrypted_message).unwrap(), message);
+    }
+
+    #[test]
+    fn test_checksum_detects_corruption() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap().with_checksum(true);
+        let mut ciphertext = aesteve.encrypt_bytes(b"low-value telemetry record");
+        ciphertext[3] ^= 0x01;
+        assert!(matches!(aesteve.decrypt_bytes(&ciphertext), Err(AESError::ChecksumMismatch)));
+    }
+}
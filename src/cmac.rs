@@ -0,0 +1,272 @@
+use crate::ct::ct_eq;
+use crate::{wipe, AESteve};
+
+/// Reduction constant for doubling in GF(2^128).
+const RB: u8 = 0x87;
+
+pub(crate) fn dbl(block: &[u8; 16]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    for i in 0..16 {
+        let carry = if i < 15 { block[i + 1] >> 7 } else { 0 };
+        out[i] = (block[i] << 1) | carry;
+    }
+    if block[0] & 0x80 != 0 {
+        out[15] ^= RB;
+    }
+    out
+}
+
+fn xor_block(a: &[u8; 16], b: &[u8]) -> [u8; 16] {
+    let mut out = *a;
+    for (o, &byte) in out.iter_mut().zip(b) {
+        *o ^= byte;
+    }
+    out
+}
+
+impl AESteve {
+    /// Returns the CMAC subkeys, deriving them on first use and caching them in the
+    /// shared key schedule so every later `cmac` call (on any clone) skips the work.
+    fn cmac_subkeys(&self) -> ([u8; 16], [u8; 16]) {
+        *self.schedule.cmac_subkeys.get_or_init(|| {
+            let l = self.encrypt_block_bytes(&[0u8; 16]);
+            let k1 = dbl(&l);
+            let k2 = dbl(&k1);
+            (k1, k2)
+        })
+    }
+
+    /// Computes the AES-CMAC (RFC 4493) of `message`.
+    ///
+    /// The message is CBC-MAC chained through the cipher. Its last block is XORed with
+    /// subkey K1 when it is complete, or padded with `0x80 0x00...` and XORed with K2
+    /// otherwise (including for an empty message). K1 and K2 are derived once from the
+    /// encryption of the zero block by doubling in GF(2^128), and cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to authenticate.
+    ///
+    /// # Returns
+    ///
+    /// * `[u8; 16]` - The authentication tag. Check tags with [`AESteve::verify_cmac`].
+    pub fn cmac(&self, message: &[u8]) -> [u8; 16] {
+        let (k1, k2) = self.cmac_subkeys();
+        let block_count = message.len().div_ceil(16).max(1);
+        let complete = !message.is_empty() && message.len().is_multiple_of(16);
+
+        let mut state = [0u8; 16];
+        for chunk in message.chunks(16).take(block_count - 1) {
+            state = self.encrypt_block_bytes(&xor_block(&state, chunk));
+        }
+
+        let tail = &message[(block_count - 1) * 16..];
+        let last = if complete {
+            xor_block(&k1, tail)
+        } else {
+            let mut padded = [0u8; 16];
+            padded[..tail.len()].copy_from_slice(tail);
+            padded[tail.len()] = 0x80;
+            xor_block(&k2, &padded)
+        };
+        self.encrypt_block_bytes(&xor_block(&state, &last))
+    }
+
+    /// Checks `tag` against the AES-CMAC of `message` in constant time.
+    ///
+    /// The recomputed tag never leaves this method, and the comparison takes the same
+    /// time wherever the tags differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message the tag claims to authenticate.
+    /// * `tag` - The tag to verify.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if `tag` is the CMAC of `message` under this key.
+    pub fn verify_cmac(&self, message: &[u8], tag: &[u8; 16]) -> bool {
+        ct_eq(&self.cmac(message), tag)
+    }
+
+    /// Derives a pseudorandom 16-byte value bound to a domain-separation label.
+    ///
+    /// The output is the CMAC of `len(label) || label || input`, where the label
+    /// length is encoded as a big-endian `u64`. Length-prefixing the label keeps
+    /// distinct `(label, input)` pairs from ever producing the same CMAC input.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The domain the output is derived for (e.g. `b"session-id"`).
+    /// * `input` - The data to derive from.
+    ///
+    /// # Returns
+    ///
+    /// * `[u8; 16]` - The derived value.
+    pub fn prf(&self, label: &[u8], input: &[u8]) -> [u8; 16] {
+        let mut data = Vec::with_capacity(8 + label.len() + input.len());
+        data.extend_from_slice(&(label.len() as u64).to_be_bytes());
+        data.extend_from_slice(label);
+        data.extend_from_slice(input);
+        self.cmac(&data)
+    }
+
+    /// Derives `out_len` pseudorandom bytes bound to `info`, for splitting one key into
+    /// several subkeys.
+    ///
+    /// Uses the NIST SP 800-108 KDF in counter mode with AES-CMAC as the PRF: block `i`
+    /// is the CMAC of `[i]32 ‖ info ‖ 0x00 ‖ [out_len * 8]32`, with big-endian counters
+    /// starting at 1. Because the output length is part of every block, asking for a
+    /// different length gives unrelated output rather than a prefix or extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - The purpose the output is derived for (e.g. `b"session keys v1"`).
+    /// * `out_len` - The number of bytes to produce.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The derived bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out_len * 8` does not fit in a `u32`.
+    pub fn prf_expand(&self, info: &[u8], out_len: usize) -> Vec<u8> {
+        let bits = out_len
+            .checked_mul(8)
+            .and_then(|bits| u32::try_from(bits).ok())
+            .expect("prf_expand output length in bits must fit in a u32");
+        let mut input = Vec::with_capacity(4 + info.len() + 1 + 4);
+        input.extend_from_slice(&[0u8; 4]);
+        input.extend_from_slice(info);
+        input.push(0x00);
+        input.extend_from_slice(&bits.to_be_bytes());
+
+        let mut output = Vec::with_capacity(out_len.next_multiple_of(16));
+        for counter in 1..=out_len.div_ceil(16) as u32 {
+            input[..4].copy_from_slice(&counter.to_be_bytes());
+            output.extend_from_slice(&self.cmac(&input));
+        }
+        output.truncate(out_len);
+        output
+    }
+
+    /// Derives an independent cipher with this instance's key size, bound to `label`.
+    ///
+    /// The subkey is [`AESteve::prf_expand`] of `label`, so constructions that need a MAC
+    /// key and a keystream key get separate keys from one master key.
+    pub(crate) fn derive_subkey(&self, label: &[u8]) -> AESteve {
+        let mut key = self.prf_expand(label, self.variant().key_len());
+        let subkey = AESteve::new(&key).unwrap();
+        wipe(&mut key);
+        subkey
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rfc4493_message() -> [u8; 64] {
+        [
+            0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+            0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+            0x30, 0xc8, 0x1c, 0x46, 0xa3, 0x5c, 0xe4, 0x11, 0xe5, 0xfb, 0xc1, 0x19, 0x1a, 0x0a, 0x52, 0xef,
+            0xf6, 0x9f, 0x24, 0x45, 0xdf, 0x4f, 0x9b, 0x17, 0xad, 0x2b, 0x41, 0x7b, 0xe6, 0x6c, 0x37, 0x10,
+        ]
+    }
+
+    #[test]
+    fn test_cmac_rfc4493_subkeys() {
+        // RFC 4493, section 4: subkey generation.
+        let key = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+        let aesteve = AESteve::new(&key).unwrap();
+        let (k1, k2) = aesteve.cmac_subkeys();
+        assert_eq!(k1, [0xfb, 0xee, 0xd6, 0x18, 0x35, 0x71, 0x33, 0x66, 0x7c, 0x85, 0xe0, 0x8f, 0x72, 0x36, 0xa8, 0xde]);
+        assert_eq!(k2, [0xf7, 0xdd, 0xac, 0x30, 0x6a, 0xe2, 0x66, 0xcc, 0xf9, 0x0b, 0xc1, 0x1e, 0xe4, 0x6d, 0x51, 0x3b]);
+    }
+
+    #[test]
+    fn test_cmac_rfc4493_vectors() {
+        // RFC 4493, section 4: examples 1 to 4.
+        let key = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+        let aesteve = AESteve::new(&key).unwrap();
+        let message = rfc4493_message();
+        let cases: [(usize, [u8; 16]); 4] = [
+            (0, [0xbb, 0x1d, 0x69, 0x29, 0xe9, 0x59, 0x37, 0x28, 0x7f, 0xa3, 0x7d, 0x12, 0x9b, 0x75, 0x67, 0x46]),
+            (16, [0x07, 0x0a, 0x16, 0xb4, 0x6b, 0x4d, 0x41, 0x44, 0xf7, 0x9b, 0xdd, 0x9d, 0xd0, 0x4a, 0x28, 0x7c]),
+            (40, [0xdf, 0xa6, 0x67, 0x47, 0xde, 0x9a, 0xe6, 0x30, 0x30, 0xca, 0x32, 0x61, 0x14, 0x97, 0xc8, 0x27]),
+            (64, [0x51, 0xf0, 0xbe, 0xbf, 0x7e, 0x3b, 0x9d, 0x92, 0xfc, 0x49, 0x74, 0x17, 0x79, 0x36, 0x3c, 0xfe]),
+        ];
+        for (len, tag) in cases {
+            assert_eq!(aesteve.cmac(&message[..len]), tag, "message length {len}");
+            assert!(aesteve.verify_cmac(&message[..len], &tag));
+        }
+    }
+
+    #[test]
+    fn test_prf_labels_differ() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let a = aesteve.prf(b"encryption", b"input");
+        let b = aesteve.prf(b"authentication", b"input");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_prf_deterministic() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        assert_eq!(aesteve.prf(b"label", b"input"), aesteve.prf(b"label", b"input"));
+    }
+
+    #[test]
+    fn test_prf_label_boundary() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        assert_ne!(aesteve.prf(b"ab", b"c"), aesteve.prf(b"a", b"bc"));
+    }
+
+    #[test]
+    fn test_verify_cmac() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let mut tag = aesteve.cmac(b"authenticated only");
+        assert!(aesteve.verify_cmac(b"authenticated only", &tag));
+        assert!(!aesteve.verify_cmac(b"authenticated onlY", &tag));
+
+        tag[7] ^= 0x10;
+        assert!(!aesteve.verify_cmac(b"authenticated only", &tag));
+    }
+
+    #[test]
+    fn test_cmac_subkeys_cached() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        assert!(aesteve.schedule.cmac_subkeys.get().is_none());
+        let first = aesteve.cmac(b"message");
+        assert!(aesteve.schedule.cmac_subkeys.get().is_some());
+        assert_eq!(aesteve.clone().cmac(b"message"), first);
+    }
+
+    #[test]
+    fn test_prf_expand() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        for out_len in [0, 1, 16, 17, 64] {
+            let output = aesteve.prf_expand(b"subkeys", out_len);
+            assert_eq!(output.len(), out_len);
+            assert_eq!(output, aesteve.prf_expand(b"subkeys", out_len));
+        }
+        assert_ne!(aesteve.prf_expand(b"subkeys", 64), aesteve.prf_expand(b"other", 64));
+        assert_ne!(aesteve.prf_expand(b"subkeys", 64)[..32], aesteve.prf_expand(b"subkeys", 32));
+    }
+
+    #[test]
+    fn test_prf_expand_sp800_108_vector() {
+        // Python `cryptography` KBKDFCMAC: AES, counter mode, rlen = llen = 4, counter
+        // before fixed input, label b"subkeys", empty context, key 00..0f.
+        let key: Vec<u8> = (0u8..16).collect();
+        let aesteve = AESteve::new(&key).unwrap();
+        let expected = [
+            0x7e, 0x4f, 0xb8, 0xfa, 0x13, 0xe1, 0x8d, 0x35, 0xcc, 0xec, 0x29, 0xc1, 0xa2, 0x97, 0xda, 0x4f,
+            0x7a, 0x8b, 0xc7, 0xf8, 0xea, 0xcf, 0x9d, 0x45, 0x48, 0x43, 0xb5, 0xfa, 0xbf, 0xa8, 0x51, 0x5b,
+            0xb9, 0xf1, 0x0b, 0xa4, 0x7d, 0x2b, 0xa2, 0x78,
+        ];
+        assert_eq!(aesteve.prf_expand(b"subkeys", 40), expected);
+    }
+}
@@ -0,0 +1,78 @@
+/// Selects between two bytes without branching on `mask`.
+///
+/// Returns `a` when `mask` is `0xFF` and `b` when `mask` is `0x00`. `mask` must be
+/// exactly one of those two values; any other mask mixes bits of `a` and `b`.
+///
+/// # Arguments
+///
+/// * `mask` - `0xFF` to select `a`, `0x00` to select `b`.
+/// * `a` - The byte returned for an all-ones mask.
+/// * `b` - The byte returned for an all-zeros mask.
+pub fn ct_select(mask: u8, a: u8, b: u8) -> u8 {
+    (a & mask) | (b & !mask)
+}
+
+/// Compares two byte slices in time independent of where they differ.
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    ct_eq_mask(a, b) == 0xFF
+}
+
+/// Compares two byte slices without branching on their contents.
+///
+/// Returns `0xFF` if they are equal and `0x00` otherwise, for combining with
+/// [`ct_mask`] before the outcome is revealed. Only the lengths, which are public,
+/// are compared with a branch.
+pub(crate) fn ct_eq_mask(a: &[u8], b: &[u8]) -> u8 {
+    if a.len() != b.len() {
+        return 0x00;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    ((std::hint::black_box(diff) as u16).wrapping_sub(1) >> 8) as u8
+}
+
+/// ANDs every byte of `buf` with `mask`, keeping it for `0xFF` and zeroing it for `0x00`.
+///
+/// The same volatile writes happen for either mask, so a failed check costs exactly
+/// as much as a successful one and the zeroing cannot be optimized away.
+pub(crate) fn ct_mask(buf: &mut [u8], mask: u8) {
+    for byte in buf.iter_mut() {
+        // SAFETY: `byte` is a valid, aligned, exclusive reference.
+        unsafe { std::ptr::write_volatile(byte, *byte & mask) };
+    }
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ct_select_all_combinations() {
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                assert_eq!(ct_select(0xFF, a, b), a);
+                assert_eq!(ct_select(0x00, a, b), b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ct_eq_mask() {
+        assert_eq!(ct_eq_mask(b"tag", b"tag"), 0xFF);
+        assert_eq!(ct_eq_mask(b"", b""), 0xFF);
+        for bit in 0..8 {
+            assert_eq!(ct_eq_mask(&[0x00], &[1 << bit]), 0x00);
+        }
+        assert_eq!(ct_eq_mask(b"tag", b"tags"), 0x00);
+    }
+
+    #[test]
+    fn test_ct_mask() {
+        let mut kept = *b"plaintext";
+        ct_mask(&mut kept, 0xFF);
+        assert_eq!(&kept, b"plaintext");
+        let mut zeroed = *b"plaintext";
+        ct_mask(&mut zeroed, 0x00);
+        assert_eq!(zeroed, [0u8; 9]);
+    }
+}
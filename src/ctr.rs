@@ -0,0 +1,230 @@
+use crate::{AESError, AESteve};
+use rayon::prelude::*;
+
+fn xor_in_place(data: &mut [u8], keystream: &[u8; 16]) {
+    for (byte, key) in data.iter_mut().zip(keystream) {
+        *byte ^= key;
+    }
+}
+
+impl AESteve {
+    /// Encrypts (or decrypts) `data` in CTR mode with a full 128-bit counter, without padding.
+    ///
+    /// `nonce` is the initial counter block: the whole 16 bytes are treated as a big-endian
+    /// integer and incremented once per block, wrapping around after `2^128 - 1`, which
+    /// matches OpenSSL's `aes-128-ctr`. Each counter block is encrypted and XORed with the
+    /// data, in parallel, and the last keystream block is truncated to the data, so the
+    /// output is as long as `data`. CTR is symmetric, so the same call decrypts. Never
+    /// reuse a nonce under one key.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted or decrypted.
+    /// * `nonce` - The initial counter block.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The transformed bytes, as long as `data`.
+    pub fn encrypt_ctr(&self, data: &[u8], nonce: &[u8; 16]) -> Vec<u8> {
+        let initial = u128::from_be_bytes(*nonce);
+        let mut output = data.to_vec();
+
+        output.par_chunks_mut(16).enumerate().for_each(|(i, chunk)| {
+            let counter = initial.wrapping_add(i as u128).to_be_bytes();
+            xor_in_place(chunk, &self.encrypt_block_bytes(&counter));
+        });
+        output
+    }
+
+    /// Encrypts (or decrypts) `data` in CTR mode. Alias of [`AESteve::encrypt_ctr`].
+    #[deprecated(note = "renamed to encrypt_ctr")]
+    pub fn encrypt_ctr_full(&self, data: &[u8], initial_counter: &[u8; 16]) -> Vec<u8> {
+        self.encrypt_ctr(data, initial_counter)
+    }
+
+    /// Encrypts (or decrypts) `data` in CTR mode with a 96-bit nonce and a 32-bit block
+    /// counter starting at `start_counter`.
+    ///
+    /// Block `i` of `data` uses the counter block `nonce ‖ [start_counter + i]32`, with the
+    /// counter big-endian and wrapping modulo `2^32`. Passing the number of blocks already
+    /// processed as `start_counter` resumes an interrupted stream where it left off, so
+    /// resumed output matches the corresponding slice of a single uninterrupted call.
+    /// Never let the counter wrap under one nonce: that reuses keystream.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted or decrypted, starting on a block boundary.
+    /// * `nonce` - The 12-byte nonce for the stream.
+    /// * `start_counter` - The counter for the first 16 bytes of `data`.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The transformed bytes.
+    pub fn encrypt_ctr_from(&self, data: &[u8], nonce: &[u8; 12], start_counter: u32) -> Vec<u8> {
+        let mut output = data.to_vec();
+
+        output.par_chunks_mut(16).enumerate().for_each(|(i, chunk)| {
+            let mut counter = [0u8; 16];
+            counter[..12].copy_from_slice(nonce);
+            counter[12..].copy_from_slice(&start_counter.wrapping_add(i as u32).to_be_bytes());
+            xor_in_place(chunk, &self.encrypt_block_bytes(&counter));
+        });
+        output
+    }
+
+    /// Encrypts (or decrypts) `data` in CTR mode like [`AESteve::encrypt_ctr`],
+    /// writing into a caller-supplied buffer without allocating.
+    ///
+    /// For real-time pipelines that must not allocate in the hot loop: blocks are
+    /// processed in order on the calling thread, and each keystream block is generated in
+    /// `keystream_scratch`, which is left holding the last one. Reuse the same `out` and
+    /// scratch across calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted or decrypted.
+    /// * `initial_counter` - The counter block for the first 16 bytes of `data`.
+    /// * `out` - Receives the transformed bytes. Must be exactly as long as `data`.
+    /// * `keystream_scratch` - Working space for one keystream block.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidDataLength` if `out.len() != data.len()`.
+    pub fn encrypt_ctr_into(
+        &self,
+        data: &[u8],
+        initial_counter: &[u8; 16],
+        out: &mut [u8],
+        keystream_scratch: &mut [u8; 16],
+    ) -> Result<(), AESError> {
+        if out.len() != data.len() {
+            return Err(AESError::InvalidDataLength);
+        }
+        let initial = u128::from_be_bytes(*initial_counter);
+        for (i, (out_chunk, chunk)) in out.chunks_mut(16).zip(data.chunks(16)).enumerate() {
+            *keystream_scratch = self.encrypt_block_bytes(&initial.wrapping_add(i as u128).to_be_bytes());
+            for ((out_byte, &byte), &key) in out_chunk.iter_mut().zip(chunk).zip(keystream_scratch.iter()) {
+                *out_byte = byte ^ key;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+    ];
+
+    #[test]
+    fn test_ctr_openssl_vector() {
+        // printf 'Counter mode streams bytes, 36 long.' | openssl enc -aes-128-ctr \
+        //     -K 2b7e151628aed2a6abf7158809cf4f3c -iv f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff
+        let aesteve = AESteve::new(&KEY).unwrap();
+        let iv = [
+            0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+        ];
+        let plaintext = b"Counter mode streams bytes, 36 long.";
+        let expected = [
+            0xaf, 0xe3, 0xaa, 0x1d, 0xec, 0x05, 0x0e, 0x90, 0x9f, 0xbd, 0x72, 0x10, 0xca, 0xed, 0xd5, 0x96,
+            0x53, 0x4a, 0x11, 0x4f, 0x47, 0x11, 0x28, 0x17, 0x7d, 0xd3, 0x5b, 0xf7, 0xcf, 0x66, 0x53, 0xc2,
+            0x05, 0x42, 0xa4, 0x56,
+        ];
+
+        let ciphertext = aesteve.encrypt_ctr(plaintext, &iv);
+        assert_eq!(ciphertext, expected);
+        assert_eq!(aesteve.encrypt_ctr(&ciphertext, &iv), plaintext);
+        #[allow(deprecated)]
+        let alias = aesteve.encrypt_ctr_full(plaintext, &iv);
+        assert_eq!(alias, ciphertext);
+    }
+
+    #[test]
+    fn test_ctr_sp800_38a() {
+        // NIST SP 800-38A, F.5.1 (CTR-AES128.Encrypt) and F.5.5 (CTR-AES256.Encrypt),
+        // truncated to 20 bytes to end on a partial block.
+        let counter = [
+            0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+        ];
+        let plaintext = [
+            0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+            0xae, 0x2d, 0x8a, 0x57,
+        ];
+        let aes128 = AESteve::new(&KEY).unwrap();
+        let ciphertext = aes128.encrypt_ctr(&plaintext, &counter);
+        assert_eq!(
+            ciphertext,
+            [
+                0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26, 0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6, 0xce,
+                0x98, 0x06, 0xf6, 0x6b,
+            ]
+        );
+        assert_eq!(aes128.encrypt_ctr(&ciphertext, &counter), plaintext);
+
+        let aes256 = AESteve::new(&[
+            0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77, 0x81,
+            0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14, 0xdf, 0xf4,
+        ])
+        .unwrap();
+        assert_eq!(
+            aes256.encrypt_ctr(&plaintext[..16], &counter),
+            [0x60, 0x1e, 0xc3, 0x13, 0x77, 0x57, 0x89, 0xa5, 0xb7, 0xa7, 0xf5, 0x04, 0xbb, 0xf3, 0xd2, 0x28]
+        );
+    }
+
+    #[test]
+    fn test_ctr_counter_wraps() {
+        // head -c 32 /dev/zero | openssl enc -aes-128-ctr \
+        //     -K 2b7e151628aed2a6abf7158809cf4f3c -iv ffffffffffffffffffffffffffffffff
+        let aesteve = AESteve::new(&KEY).unwrap();
+        let keystream = aesteve.encrypt_ctr(&[0u8; 32], &[0xff; 16]);
+        assert_eq!(
+            keystream[..16],
+            [0x8a, 0xf2, 0x86, 0x01, 0x42, 0xf7, 0x86, 0xf4, 0x09, 0x30, 0x7c, 0x1a, 0x3f, 0x7e, 0xaa, 0xac]
+        );
+        assert_eq!(keystream[16..], aesteve.encrypt_block_bytes(&[0u8; 16]));
+    }
+
+    #[test]
+    fn test_ctr_from_resumes() {
+        let aesteve = AESteve::new(&KEY).unwrap();
+        let nonce = [0x42u8; 12];
+        let data: Vec<u8> = (0u8..32).collect();
+
+        let whole = aesteve.encrypt_ctr_from(&data, &nonce, 0);
+        assert_eq!(aesteve.encrypt_ctr_from(&data[16..], &nonce, 1), whole[16..]);
+        assert_eq!(aesteve.encrypt_ctr_from(&whole, &nonce, 0), data);
+
+        let mut initial_counter = [0u8; 16];
+        initial_counter[..12].copy_from_slice(&nonce);
+        initial_counter[12..].copy_from_slice(&7u32.to_be_bytes());
+        assert_eq!(aesteve.encrypt_ctr_from(&data, &nonce, 7), aesteve.encrypt_ctr(&data, &initial_counter));
+    }
+
+    #[test]
+    fn test_ctr_from_counter_wraps() {
+        let aesteve = AESteve::new(&KEY).unwrap();
+        let nonce = [0x42u8; 12];
+        let keystream = aesteve.encrypt_ctr_from(&[0u8; 32], &nonce, u32::MAX);
+        assert_eq!(keystream[16..], aesteve.encrypt_ctr_from(&[0u8; 16], &nonce, 0));
+    }
+
+    #[test]
+    fn test_ctr_into() {
+        let aesteve = AESteve::new(&KEY).unwrap();
+        let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+        let mut out = vec![0u8; data.len()];
+        let mut scratch = [0u8; 16];
+
+        aesteve.encrypt_ctr_into(&data, &[0xff; 16], &mut out, &mut scratch).unwrap();
+        assert_eq!(out, aesteve.encrypt_ctr(&data, &[0xff; 16]));
+
+        assert!(matches!(
+            aesteve.encrypt_ctr_into(&data, &[0xff; 16], &mut out[..999], &mut scratch),
+            Err(AESError::InvalidDataLength)
+        ));
+    }
+}
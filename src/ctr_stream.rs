@@ -0,0 +1,243 @@
+use crate::AESteve;
+#[cfg(feature = "async")]
+use bytes::Bytes;
+#[cfg(feature = "async")]
+use futures_core::Stream;
+use std::io::{self, Read, Write};
+#[cfg(feature = "async")]
+use std::pin::Pin;
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
+
+/// The CTR keystream as a byte position: the counter of the current block and how much
+/// of its keystream has been used. Not `Clone`, so the counter can never be duplicated.
+#[derive(Debug)]
+struct Keystream {
+    cipher: AESteve,
+    counter: u128,
+    block: [u8; 16],
+    offset: usize,
+}
+
+impl Keystream {
+    fn new(cipher: &AESteve, initial_counter: &[u8; 16]) -> Self {
+        // Starting one block early with the block used up makes the first byte
+        // advance onto `initial_counter`.
+        Keystream {
+            cipher: cipher.clone(),
+            counter: u128::from_be_bytes(*initial_counter).wrapping_sub(1),
+            block: [0u8; 16],
+            offset: 16,
+        }
+    }
+
+    fn apply(&mut self, data: &mut [u8]) {
+        for byte in data {
+            if self.offset == 16 {
+                self.counter = self.counter.wrapping_add(1);
+                self.block = self.cipher.encrypt_block_bytes(&self.counter.to_be_bytes());
+                self.offset = 0;
+            }
+            *byte ^= self.block[self.offset];
+            self.offset += 1;
+        }
+    }
+}
+
+/// Decrypts (or encrypts) everything read from `R` in CTR mode.
+///
+/// Uses the same full 128-bit counter as [`AESteve::encrypt_ctr`], so reading data
+/// encrypted by it, or by a [`CtrWriter`] with the same counter, yields the plaintext.
+/// Bytes are transformed as they pass through: no buffering, padding or length change.
+///
+/// Deliberately not `Clone`: a copy would carry on from the same counter and reuse the
+/// keystream, which breaks CTR completely. Start another stream with a fresh counter
+/// instead; the `AESteve` it was built from stays cheaply `Clone`.
+///
+/// ```compile_fail
+/// use aesteve::{AESteve, CtrReader};
+///
+/// let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+/// let reader = CtrReader::new(&b"ciphertext"[..], &aesteve, &[0u8; 16]);
+/// let copy = reader.clone();
+/// ```
+#[derive(Debug)]
+pub struct CtrReader<R> {
+    inner: R,
+    keystream: Keystream,
+}
+
+impl<R: Read> CtrReader<R> {
+    /// Wraps `inner`, starting the keystream at `initial_counter`.
+    pub fn new(inner: R, cipher: &AESteve, initial_counter: &[u8; 16]) -> Self {
+        CtrReader { inner, keystream: Keystream::new(cipher, initial_counter) }
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for CtrReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.keystream.apply(&mut buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Encrypts (or decrypts) everything written to it in CTR mode before passing it to `W`.
+///
+/// The counter matches [`AESteve::encrypt_ctr`] and [`CtrReader`]. Each `write` is
+/// transformed and forwarded in full with `write_all`; after an I/O error the keystream
+/// position is no longer known and the writer should be discarded.
+///
+/// Deliberately not `Clone`, for the same reason as [`CtrReader`]: two writers sharing a
+/// counter would encrypt different data under the same keystream.
+///
+/// ```compile_fail
+/// use aesteve::{AESteve, CtrWriter};
+///
+/// let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+/// let writer = CtrWriter::new(Vec::new(), &aesteve, &[0u8; 16]);
+/// let copy = writer.clone();
+/// ```
+#[derive(Debug)]
+pub struct CtrWriter<W> {
+    inner: W,
+    keystream: Keystream,
+}
+
+impl<W: Write> CtrWriter<W> {
+    /// Wraps `inner`, starting the keystream at `initial_counter`.
+    pub fn new(inner: W, cipher: &AESteve, initial_counter: &[u8; 16]) -> Self {
+        CtrWriter { inner, keystream: Keystream::new(cipher, initial_counter) }
+    }
+
+    /// Returns the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for CtrWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut transformed = buf.to_vec();
+        self.keystream.apply(&mut transformed);
+        self.inner.write_all(&transformed)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Encrypts (or decrypts) every chunk yielded by an async `Stream` of bytes in CTR mode.
+///
+/// The async counterpart of [`CtrWriter`]: chunks of any size pass through transformed
+/// and unchanged in length, and the keystream position carries over from one chunk to
+/// the next, so the concatenated output matches [`AESteve::encrypt_ctr`]. Polling
+/// is delegated to the inner stream, so backpressure is preserved, and the adapter is
+/// runtime-agnostic. Requires the `async` feature.
+///
+/// Not `Clone`, for the same reason as [`CtrReader`].
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub struct CtrStream<S> {
+    inner: S,
+    keystream: Keystream,
+}
+
+#[cfg(feature = "async")]
+impl<S: Stream<Item = Bytes> + Unpin> CtrStream<S> {
+    /// Wraps `inner`, starting the keystream at `initial_counter`.
+    pub fn new(inner: S, cipher: &AESteve, initial_counter: &[u8; 16]) -> Self {
+        CtrStream { inner, keystream: Keystream::new(cipher, initial_counter) }
+    }
+
+    /// Returns the wrapped stream.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+#[cfg(feature = "async")]
+impl<S: Stream<Item = Bytes> + Unpin> Stream for CtrStream<S> {
+    type Item = Bytes;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        let this = &mut *self;
+        Pin::new(&mut this.inner).poll_next(cx).map(|chunk| {
+            chunk.map(|chunk| {
+                let mut transformed = Vec::from(chunk);
+                this.keystream.apply(&mut transformed);
+                Bytes::from(transformed)
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ctr_stream_odd_chunks() {
+        let aesteve = AESteve::new(&[0x11u8; 16]).unwrap();
+        let counter = [0xffu8; 16];
+        let plaintext: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+
+        let mut writer = CtrWriter::new(Vec::new(), &aesteve, &counter);
+        for chunk in plaintext.chunks(13) {
+            writer.write_all(chunk).unwrap();
+        }
+        let ciphertext = writer.into_inner();
+        assert_eq!(ciphertext, aesteve.encrypt_ctr(&plaintext, &counter));
+
+        let mut reader = CtrReader::new(ciphertext.as_slice(), &aesteve, &counter);
+        let mut decrypted = Vec::new();
+        let mut buf = [0u8; 7];
+        loop {
+            let n = reader.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            decrypted.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_ctr_stream_async_chunks() {
+        use futures::executor::block_on;
+        use futures::stream::{self, StreamExt};
+
+        let aesteve = AESteve::new(&[0x11u8; 16]).unwrap();
+        let counter = [0xffu8; 16];
+        let plaintext: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+        let chunks: Vec<Bytes> = [0, 13, 3, 16, 100, 0, 868]
+            .iter()
+            .scan(0, |start, &len| {
+                let chunk = Bytes::copy_from_slice(&plaintext[*start..*start + len]);
+                *start += len;
+                Some(chunk)
+            })
+            .collect();
+
+        let encrypted: Vec<Bytes> = block_on(CtrStream::new(stream::iter(chunks), &aesteve, &counter).collect());
+        assert_eq!(encrypted.len(), 7);
+        let ciphertext = encrypted.concat();
+        assert_eq!(ciphertext, aesteve.encrypt_ctr(&plaintext, &counter));
+
+        let resplit = ciphertext.chunks(64).map(Bytes::copy_from_slice).collect::<Vec<_>>();
+        let decrypted: Vec<Bytes> = block_on(CtrStream::new(stream::iter(resplit), &aesteve, &counter).collect());
+        assert_eq!(decrypted.concat(), plaintext);
+    }
+}
@@ -0,0 +1,138 @@
+use base64::engine::general_purpose::{STANDARD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+
+use crate::AESError;
+
+/// Text encodings for ciphertext returned by `encrypt_ecb` and accepted by `decrypt_ecb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// Standard Base64 (RFC 4648 §4) with `=` padding.
+    #[default]
+    Base64,
+    /// URL-safe Base64 (RFC 4648 §5) with `=` padding.
+    Base64Url,
+    /// URL-safe Base64 without padding, as used by JWTs.
+    Base64UrlNoPad,
+}
+
+impl Encoding {
+    pub(crate) fn encode(&self, bytes: &[u8]) -> String {
+        let mut text = String::with_capacity(self.encoded_len(bytes.len()));
+        self.encode_into(bytes, &mut text);
+        text
+    }
+
+    /// Appends the encoding of `bytes` to `text`.
+    pub(crate) fn encode_into(&self, bytes: &[u8], text: &mut String) {
+        match self {
+            Encoding::Base64 => STANDARD.encode_string(bytes, text),
+            Encoding::Base64Url => URL_SAFE.encode_string(bytes, text),
+            Encoding::Base64UrlNoPad => URL_SAFE_NO_PAD.encode_string(bytes, text),
+        }
+    }
+
+    /// Returns the length of the text `len` bytes encode to.
+    ///
+    /// Padded Base64 emits 4 characters per started group of 3 bytes; unpadded Base64
+    /// drops the trailing `=`.
+    pub(crate) fn encoded_len(&self, len: usize) -> usize {
+        match self {
+            Encoding::Base64 | Encoding::Base64Url => len.div_ceil(3) * 4,
+            Encoding::Base64UrlNoPad => (len * 4).div_ceil(3),
+        }
+    }
+
+    /// Returns the number of bytes `text` decodes to, assuming it is valid in this encoding.
+    ///
+    /// Each Base64 character carries 6 bits; trailing `=` padding carries none.
+    pub(crate) fn decoded_len(text: &str) -> usize {
+        text.trim_end_matches('=').len() * 3 / 4
+    }
+
+    pub(crate) fn decode(&self, text: &str) -> Result<Vec<u8>, base64::DecodeError> {
+        match self {
+            Encoding::Base64 => STANDARD.decode(text),
+            Encoding::Base64Url => URL_SAFE.decode(text),
+            Encoding::Base64UrlNoPad => URL_SAFE_NO_PAD.decode(text),
+        }
+    }
+}
+
+/// Converts ciphertext text from one encoding to another without decrypting it.
+///
+/// Only the text representation changes; the ciphertext bytes, and so the key and
+/// plaintext, are untouched.
+///
+/// # Arguments
+///
+/// * `ciphertext` - The ciphertext encoded with `from`.
+/// * `from` - The encoding `ciphertext` is currently in.
+/// * `to` - The encoding to convert to.
+///
+/// # Returns
+///
+/// * `String` - The same ciphertext encoded with `to`.
+///
+/// # Errors
+///
+/// Returns `AESError::InvalidBase64` if `ciphertext` is not valid in the `from` encoding.
+pub fn reencode(ciphertext: &str, from: Encoding, to: Encoding) -> Result<String, AESError> {
+    Ok(to.encode(&from.decode(ciphertext)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AESteve;
+
+    #[test]
+    fn test_url_no_pad_round_trip() {
+        let aesteve = AESteve::new(&[3u8; 16]).unwrap().with_encoding(Encoding::Base64UrlNoPad);
+        for len in 0..64 {
+            let message: String = "jwt~payload?".chars().cycle().take(len).collect();
+            let encrypted_message = aesteve.encrypt_ecb(message.clone()).unwrap();
+            assert!(!encrypted_message.contains(['=', '+', '/']));
+            assert_eq!(aesteve.decrypt_ecb(encrypted_message).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_decoded_len() {
+        for len in 0..40 {
+            let bytes = vec![0xA5u8; len];
+            for encoding in [Encoding::Base64, Encoding::Base64Url, Encoding::Base64UrlNoPad] {
+                assert_eq!(Encoding::decoded_len(&encoding.encode(&bytes)), len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        for len in 0..40 {
+            let bytes = vec![0xA5u8; len];
+            for encoding in [Encoding::Base64, Encoding::Base64Url, Encoding::Base64UrlNoPad] {
+                assert_eq!(encoding.encoded_len(len), encoding.encode(&bytes).len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_encodings_share_ciphertext() {
+        let aesteve = AESteve::new(&[3u8; 16]).unwrap();
+        let standard = aesteve.encrypt_ecb(String::from("same bytes")).unwrap();
+        let url = aesteve.with_encoding(Encoding::Base64Url).encrypt_ecb(String::from("same bytes")).unwrap();
+        assert_eq!(Encoding::Base64.decode(&standard).unwrap(), Encoding::Base64Url.decode(&url).unwrap());
+    }
+
+    #[test]
+    fn test_reencode_round_trip() {
+        let aesteve = AESteve::new(&[3u8; 16]).unwrap();
+        let standard = aesteve.encrypt_ecb(String::from("stored before the URL switch??>")).unwrap();
+        let url = reencode(&standard, Encoding::Base64, Encoding::Base64Url).unwrap();
+        assert!(!url.contains(['+', '/']));
+
+        let url_aesteve = AESteve::new(&[3u8; 16]).unwrap().with_encoding(Encoding::Base64Url);
+        assert_eq!(url_aesteve.decrypt_ecb(url.clone()).unwrap(), "stored before the URL switch??>");
+        assert_eq!(reencode(&url, Encoding::Base64Url, Encoding::Base64).unwrap(), standard);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{AESError, AESteve};
+
+/// Leading bytes identifying ciphertext written by this crate.
+pub(crate) const MAGIC: &[u8; 4] = b"AESV";
+/// The current envelope format: `MAGIC ‖ VERSION ‖ variant ‖ payload`.
+pub(crate) const VERSION: u8 = 1;
+pub(crate) const HEADER_LEN: usize = MAGIC.len() + 2;
+
+/// The AES key sizes an envelope can record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AesVariant {
+    /// AES with a 128-bit key.
+    Aes128,
+    /// AES with a 192-bit key.
+    Aes192,
+    /// AES with a 256-bit key.
+    Aes256,
+}
+
+impl AesVariant {
+    /// Returns the key length in bytes, which is also the variant's byte in the envelope.
+    pub fn key_len(&self) -> usize {
+        match self {
+            AesVariant::Aes128 => 16,
+            AesVariant::Aes192 => 24,
+            AesVariant::Aes256 => 32,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            16 => Some(AesVariant::Aes128),
+            24 => Some(AesVariant::Aes192),
+            32 => Some(AesVariant::Aes256),
+            _ => None,
+        }
+    }
+}
+
+/// Builds the envelope header for ciphertext produced under `variant`.
+pub(crate) fn envelope_header(variant: AesVariant) -> [u8; HEADER_LEN] {
+    let mut header = [0u8; HEADER_LEN];
+    header[..4].copy_from_slice(MAGIC);
+    header[4] = VERSION;
+    header[5] = variant.key_len() as u8;
+    header
+}
+
+/// Reads which AES variant produced an enveloped ciphertext, without decrypting it.
+///
+/// Lets a store holding both AES-128 and AES-256 ciphertext pick the right key during a
+/// migration. Only works on the self-describing envelope format, not on raw or Base64
+/// ciphertext without a header.
+///
+/// # Arguments
+///
+/// * `envelope` - The raw enveloped ciphertext.
+///
+/// # Returns
+///
+/// * `AesVariant` - The variant recorded in the header.
+///
+/// # Errors
+///
+/// Returns `AESError::BadMagic` if `envelope` does not start with the envelope magic, or
+/// `AESError::UnsupportedVersion` if its version or variant is unknown to this release.
+pub fn detect_variant(envelope: &[u8]) -> Result<AesVariant, AESError> {
+    if envelope.len() < HEADER_LEN || &envelope[..4] != MAGIC {
+        return Err(AESError::BadMagic);
+    }
+    if envelope[4] != VERSION {
+        return Err(AESError::UnsupportedVersion);
+    }
+    AesVariant::from_byte(envelope[5]).ok_or(AESError::UnsupportedVersion)
+}
+
+impl AESteve {
+    /// Prefixes raw ciphertext with a self-describing envelope header.
+    ///
+    /// When enabled, `encrypt_bytes` (and everything built on it, such as `encrypt_ecb`)
+    /// writes `b"AESV" ‖ version ‖ variant` before the ciphertext, and `decrypt_bytes`
+    /// validates and strips it. This lets the format evolve and lets foreign data be
+    /// rejected up front. Defaults to disabled, which keeps the bare ciphertext output.
+    pub fn with_envelope(mut self, enabled: bool) -> Self {
+        self.envelope = enabled;
+        self
+    }
+
+    /// Prepends the envelope header to `ciphertext` if envelopes are enabled.
+    pub(crate) fn seal_envelope(&self, ciphertext: Vec<u8>) -> Vec<u8> {
+        if !self.envelope {
+            return ciphertext;
+        }
+        let mut enveloped = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+        enveloped.extend_from_slice(&envelope_header(self.variant()));
+        enveloped.extend_from_slice(&ciphertext);
+        enveloped
+    }
+
+    /// Validates and strips the envelope header if envelopes are enabled.
+    pub(crate) fn open_envelope<'a>(&self, ciphertext: &'a [u8]) -> Result<&'a [u8], AESError> {
+        if !self.envelope {
+            return Ok(ciphertext);
+        }
+        if detect_variant(ciphertext)? != self.variant() {
+            return Err(AESError::InvalidKeyLength);
+        }
+        Ok(&ciphertext[HEADER_LEN..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_variant() {
+        for variant in [AesVariant::Aes128, AesVariant::Aes256] {
+            let mut envelope = envelope_header(variant).to_vec();
+            envelope.extend_from_slice(&[0xA5; 32]);
+            assert_eq!(detect_variant(&envelope).unwrap(), variant);
+        }
+
+        let aes256 = AESteve::new(&[4u8; 32]).unwrap().with_envelope(true);
+        let enveloped = aes256.encrypt_bytes(b"recorded as AES-256");
+        assert_eq!(detect_variant(&enveloped).unwrap(), AesVariant::Aes256);
+        assert_eq!(aes256.decrypt_bytes(&enveloped).unwrap(), b"recorded as AES-256");
+    }
+
+    #[test]
+    fn test_detect_variant_rejects_foreign_data() {
+        assert!(matches!(detect_variant(b"not an envelope"), Err(AESError::BadMagic)));
+        assert!(matches!(detect_variant(b"AESV"), Err(AESError::BadMagic)));
+        assert!(matches!(detect_variant(b"AESV\x02\x10payload"), Err(AESError::UnsupportedVersion)));
+        assert!(matches!(detect_variant(b"AESV\x01\x11payload"), Err(AESError::UnsupportedVersion)));
+    }
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let aesteve = AESteve::new(&[4u8; 16]).unwrap().with_envelope(true);
+        let ciphertext = aesteve.encrypt_bytes(b"future-proof");
+        assert_eq!(&ciphertext[..HEADER_LEN], b"AESV\x01\x10");
+        assert_eq!(detect_variant(&ciphertext).unwrap(), AesVariant::Aes128);
+        assert_eq!(aesteve.decrypt_bytes(&ciphertext).unwrap(), b"future-proof");
+
+        let text = aesteve.encrypt_ecb(String::from("future-proof")).unwrap();
+        assert_eq!(aesteve.decrypt_ecb(text.clone()).unwrap(), "future-proof");
+        assert!(aesteve.verify_decryptable(&text));
+        assert_eq!(aesteve.decrypt_auto(text.as_bytes()).unwrap(), "future-proof");
+    }
+
+    #[test]
+    fn test_envelope_rejects_bad_magic_and_versions() {
+        let plain = AESteve::new(&[4u8; 16]).unwrap();
+        let aesteve = AESteve::new(&[4u8; 16]).unwrap().with_envelope(true);
+        let bare = plain.encrypt_bytes(b"no header");
+        assert!(matches!(aesteve.decrypt_bytes(&bare), Err(AESError::BadMagic)));
+
+        let mut future = aesteve.encrypt_bytes(b"from a newer release");
+        future[4] = VERSION + 1;
+        assert!(matches!(aesteve.decrypt_bytes(&future), Err(AESError::UnsupportedVersion)));
+
+        let mut aes256 = aesteve.encrypt_bytes(b"wrong variant");
+        aes256[5] = 32;
+        assert!(matches!(aesteve.decrypt_bytes(&aes256), Err(AESError::InvalidKeyLength)));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{AESError, AESteve};
+use std::collections::HashMap;
+
+impl AESteve {
+    /// Field-level encryption: encrypts the values of the listed `fields` of a record like
+    /// [`AESteve::encrypt_ecb`], leaving every other field untouched.
+    ///
+    /// Listed fields that are not in `map` are ignored. The input map is not modified.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - The record, as field name to value.
+    /// * `fields` - The names of the fields to encrypt.
+    ///
+    /// # Returns
+    ///
+    /// * `HashMap<String, String>` - A copy of `map` with the listed values replaced by their
+    ///   ciphertext in the configured encoding (Base64 by default).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError` if an error occurs during encryption.
+    pub fn encrypt_fields(&self, map: &HashMap<String, String>, fields: &[&str]) -> Result<HashMap<String, String>, AESError> {
+        self.map_fields(map, fields, |value| self.encrypt_ecb(value.to_owned()))
+    }
+
+    /// Decrypts the listed `fields` of a record produced by [`AESteve::encrypt_fields`],
+    /// leaving every other field untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - The record, as field name to value.
+    /// * `fields` - The names of the encrypted fields.
+    ///
+    /// # Returns
+    ///
+    /// * `HashMap<String, String>` - A copy of `map` with the listed values decrypted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `AESError` hit while decrypting one of the listed fields.
+    pub fn decrypt_fields(&self, map: &HashMap<String, String>, fields: &[&str]) -> Result<HashMap<String, String>, AESError> {
+        self.map_fields(map, fields, |value| self.decrypt_ecb(value.to_owned()))
+    }
+
+    fn map_fields(
+        &self,
+        map: &HashMap<String, String>,
+        fields: &[&str],
+        transform: impl Fn(&str) -> Result<String, AESError>,
+    ) -> Result<HashMap<String, String>, AESError> {
+        map.iter()
+            .map(|(name, value)| {
+                let value = if fields.contains(&name.as_str()) { transform(value)? } else { value.clone() };
+                Ok((name.clone(), value))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> HashMap<String, String> {
+        [("id", "42"), ("name", "Ada Lovelace"), ("email", "ada@example.com")]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_fields_round_trip() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let record = record();
+        let encrypted = aesteve.encrypt_fields(&record, &["name", "email"]).unwrap();
+        assert_eq!(encrypted["id"], "42");
+        assert_eq!(encrypted["name"], aesteve.encrypt_ecb("Ada Lovelace".to_string()).unwrap());
+        assert_ne!(encrypted["email"], record["email"]);
+
+        let decrypted = aesteve.decrypt_fields(&encrypted, &["name", "email"]).unwrap();
+        assert_eq!(decrypted, record);
+    }
+
+    #[test]
+    fn test_fields_missing_or_invalid() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let record = record();
+        assert_eq!(aesteve.encrypt_fields(&record, &["phone"]).unwrap(), record);
+        assert!(aesteve.decrypt_fields(&record, &["name"]).is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use sha2::{Digest, Sha256};
+
+/// Returns the SHA-256 of `ciphertext`, a stable key for indexing or deduplicating it.
+///
+/// Pairs with [`AESteve::encrypt_deterministic`](crate::AESteve::encrypt_deterministic):
+/// equal plaintexts give equal ciphertexts and therefore equal fingerprints, so an
+/// encrypted store can dedup without decrypting or pulling in its own hash dependency.
+///
+/// # Arguments
+///
+/// * `ciphertext` - The raw ciphertext.
+///
+/// # Returns
+///
+/// * `[u8; 32]` - The SHA-256 digest of `ciphertext`.
+pub fn ciphertext_fingerprint(ciphertext: &[u8]) -> [u8; 32] {
+    Sha256::digest(ciphertext).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AESteve;
+
+    #[test]
+    fn test_fingerprint_dedup() {
+        let aesteve = AESteve::new(&[0x33u8; 16]).unwrap();
+        let a = aesteve.encrypt_deterministic(b"same document");
+        let b = aesteve.encrypt_deterministic(b"same document");
+        let c = aesteve.encrypt_deterministic(b"other document");
+        assert_eq!(ciphertext_fingerprint(&a), ciphertext_fingerprint(&b));
+        assert_ne!(ciphertext_fingerprint(&a), ciphertext_fingerprint(&c));
+    }
+
+    #[test]
+    fn test_fingerprint_is_sha256() {
+        // FIPS 180-2, appendix B.1.
+        let expected = [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23,
+            0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+        ];
+        assert_eq!(ciphertext_fingerprint(b"abc"), expected);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{AESError, AESteve, Padding};
+
+/// Reads the plaintext length of an [`AESteve::encrypt_framed`] ciphertext without
+/// decrypting it or needing the key.
+///
+/// Checks that the length prefix is consistent with the ciphertext size, nothing more.
+/// The prefix is in the clear and not authenticated, so an attacker can set it to any
+/// value consistent with a ciphertext they control: fine for display, but bound it
+/// before using it to size an allocation.
+///
+/// # Arguments
+///
+/// * `framed` - The length prefix followed by the raw ciphertext.
+///
+/// # Returns
+///
+/// * `usize` - The length of the plaintext `decrypt_framed` would return.
+///
+/// # Errors
+///
+/// Returns `AESError::CiphertextTooShort` if `framed` is too short to hold a prefix and
+/// one block, or `AESError::InvalidPlaintextLength` if the prefix does not match the
+/// ciphertext length.
+pub fn plaintext_len(framed: &[u8]) -> Result<usize, AESError> {
+    if framed.len() < 4 + AESteve::min_ciphertext_len() {
+        return Err(AESError::CiphertextTooShort);
+    }
+    let (len, ciphertext) = framed.split_at(4);
+    let len = u32::from_be_bytes(len.try_into().unwrap()) as usize;
+    if ciphertext.len() != (len / 16 + 1) * 16 {
+        return Err(AESError::InvalidPlaintextLength);
+    }
+    Ok(len)
+}
+
+impl AESteve {
+    /// Encrypts `message` in ECB mode behind a 4-byte big-endian plaintext length.
+    ///
+    /// The output is `[len]32 ‖ ciphertext`. Carrying the exact length means decryption
+    /// truncates instead of guessing where padding starts, so messages ending in bytes
+    /// that look like padding survive intact. The prefix is in the clear and, like ECB
+    /// itself, unauthenticated.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The bytes to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The length prefix followed by the raw ciphertext.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `message` is 4 GiB or longer.
+    pub fn encrypt_framed(&self, message: &[u8]) -> Vec<u8> {
+        let len = u32::try_from(message.len()).expect("framed messages must be shorter than 4 GiB");
+        let mut framed = len.to_be_bytes().to_vec();
+        framed.extend_from_slice(&self.encrypt_with_padding(message, &Padding::Iso7816));
+        framed
+    }
+
+    /// Decrypts output of [`AESteve::encrypt_framed`], truncating to the carried length.
+    ///
+    /// # Arguments
+    ///
+    /// * `framed` - The length prefix followed by the raw ciphertext.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::CiphertextTooShort` if `framed` is too short to hold a prefix and
+    /// one block, or `AESError::InvalidPlaintextLength` if the prefix does not match the
+    /// ciphertext length.
+    pub fn decrypt_framed(&self, framed: &[u8]) -> Result<Vec<u8>, AESError> {
+        let len = plaintext_len(framed)?;
+        self.decrypt_exact(&framed[4..], len)
+    }
+
+    /// Encrypts `message` padded to exactly `total_len` bytes, hiding its true length.
+    ///
+    /// The plaintext is `[len]32 ‖ message ‖ 0x00…` filling `total_len` bytes, encrypted in
+    /// ECB mode, so every message encrypted with the same `total_len` has the same size.
+    /// The length lives inside the encryption, unlike [`AESteve::encrypt_framed`].
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The bytes to be encrypted.
+    /// * `total_len` - The ciphertext length; a nonzero multiple of 16.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext, exactly `total_len` bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidDataLength` if `total_len` is not a nonzero multiple of 16,
+    /// or `AESError::InvalidPlaintextLength` if `message` plus its 4-byte length does not
+    /// fit in `total_len`.
+    pub fn encrypt_padded_to(&self, message: &[u8], total_len: usize) -> Result<Vec<u8>, AESError> {
+        if total_len == 0 || !total_len.is_multiple_of(16) {
+            return Err(AESError::InvalidDataLength);
+        }
+        let len = u32::try_from(message.len()).map_err(|_| AESError::InvalidPlaintextLength)?;
+        if message.len() > total_len - 4 {
+            return Err(AESError::InvalidPlaintextLength);
+        }
+        let mut padded = Vec::with_capacity(total_len);
+        padded.extend_from_slice(&len.to_be_bytes());
+        padded.extend_from_slice(message);
+        padded.resize(total_len, 0x00);
+        Ok(self.encrypt_blocks(padded))
+    }
+
+    /// Decrypts output of [`AESteve::encrypt_padded_to`], recovering the original message.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The raw ciphertext.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted message.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::CiphertextTooShort` if `ciphertext` is shorter than one block,
+    /// `AESError::InvalidCiphertextLength` if it is not a whole number of blocks, or
+    /// `AESError::InvalidPlaintextLength` if the recovered length does not fit.
+    pub fn decrypt_padded(&self, ciphertext: &[u8]) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < Self::min_ciphertext_len() {
+            return Err(AESError::CiphertextTooShort);
+        }
+        if !ciphertext.len().is_multiple_of(16) {
+            return Err(AESError::InvalidCiphertextLength);
+        }
+        let mut padded = self.decrypt_blocks(ciphertext);
+        let len = u32::from_be_bytes(padded[..4].try_into().unwrap()) as usize;
+        if len > padded.len() - 4 {
+            return Err(AESError::InvalidPlaintextLength);
+        }
+        padded.truncate(4 + len);
+        padded.drain(..4);
+        Ok(padded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_framed_binary_round_trip() {
+        let aesteve = AESteve::new(&[8u8; 16]).unwrap();
+        let messages: [&[u8]; 4] = [
+            b"",
+            b"\x00\x00\x00",
+            b"ends in marker\x80",
+            &[0x01, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        ];
+        for message in messages {
+            let framed = aesteve.encrypt_framed(message);
+            assert_eq!(&framed[..4], &(message.len() as u32).to_be_bytes());
+            assert_eq!(aesteve.decrypt_framed(&framed).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_framed_length_mismatch() {
+        let aesteve = AESteve::new(&[8u8; 16]).unwrap();
+        let mut framed = aesteve.encrypt_framed(b"twelve bytes");
+        framed[3] = 40;
+        assert!(matches!(aesteve.decrypt_framed(&framed), Err(AESError::InvalidPlaintextLength)));
+        assert!(matches!(aesteve.decrypt_framed(&framed[..10]), Err(AESError::CiphertextTooShort)));
+    }
+
+    #[test]
+    fn test_plaintext_len() {
+        let aesteve = AESteve::new(&[8u8; 16]).unwrap();
+        for len in [0, 15, 16, 100] {
+            assert_eq!(plaintext_len(&aesteve.encrypt_framed(&vec![1u8; len])).unwrap(), len);
+        }
+        let mut framed = aesteve.encrypt_framed(b"twelve bytes");
+        framed[3] = 40;
+        assert!(matches!(plaintext_len(&framed), Err(AESError::InvalidPlaintextLength)));
+        assert!(matches!(plaintext_len(&framed[..10]), Err(AESError::CiphertextTooShort)));
+    }
+
+    #[test]
+    fn test_padded_to_round_trip() {
+        let aesteve = AESteve::new(&[8u8; 16]).unwrap();
+        for message in [&b""[..], b"hi", b"ends in zeros\x00\x00", &[0x42; 60]] {
+            let ciphertext = aesteve.encrypt_padded_to(message, 64).unwrap();
+            assert_eq!(aesteve.decrypt_padded(&ciphertext).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_padded_to_fixed_length() {
+        let aesteve = AESteve::new(&[8u8; 16]).unwrap();
+        for len in [0, 1, 12, 13, 100, 252] {
+            assert_eq!(aesteve.encrypt_padded_to(&vec![7u8; len], 256).unwrap().len(), 256);
+        }
+        assert!(matches!(aesteve.encrypt_padded_to(&[7u8; 253], 256), Err(AESError::InvalidPlaintextLength)));
+        assert!(matches!(aesteve.encrypt_padded_to(b"hi", 20), Err(AESError::InvalidDataLength)));
+        assert!(matches!(aesteve.encrypt_padded_to(b"", 0), Err(AESError::InvalidDataLength)));
+        assert!(matches!(aesteve.decrypt_padded(&[0u8; 20]), Err(AESError::InvalidCiphertextLength)));
+    }
+}
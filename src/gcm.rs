@@ -0,0 +1,266 @@
+use crate::ct::{ct_eq_mask, ct_mask};
+use crate::{AESError, AESteve};
+use rayon::prelude::*;
+
+/// The longest plaintext GCM can encrypt under one nonce: `2^32 - 2` blocks. One more
+/// and the 32-bit counter would wrap back to J0, whose keystream block masks the tag.
+pub(crate) const GCM_MAX_LEN: u64 = ((1 << 32) - 2) * 16;
+
+/// Reports whether `len` bytes fit within [`GCM_MAX_LEN`].
+pub(crate) fn gcm_len_ok(len: usize) -> bool {
+    len as u64 <= GCM_MAX_LEN
+}
+
+/// The GCM reduction polynomial, x^128 + x^7 + x^2 + x + 1, in GCM's reflected bit order.
+const R: u128 = 0xe1 << 120;
+
+/// Multiplies two elements of GF(2^128) without branching on their values.
+fn gf_mul(x: u128, y: u128) -> u128 {
+    let mut z = 0u128;
+    let mut v = y;
+    for i in 0..128 {
+        let bit = (x >> (127 - i)) & 1;
+        z ^= v & bit.wrapping_neg();
+        let lsb = v & 1;
+        v = (v >> 1) ^ (R & lsb.wrapping_neg());
+    }
+    z
+}
+
+fn ghash_update(h: u128, mut y: u128, data: &[u8]) -> u128 {
+    for chunk in data.chunks(16) {
+        let mut block = [0u8; 16];
+        block[..chunk.len()].copy_from_slice(chunk);
+        y = gf_mul(y ^ u128::from_be_bytes(block), h);
+    }
+    y
+}
+
+fn ghash(h: u128, aad: &[u8], ciphertext: &[u8]) -> u128 {
+    let y = ghash_update(h, 0, aad);
+    let y = ghash_update(h, y, ciphertext);
+    let lengths = ((aad.len() as u128 * 8) << 64) | (ciphertext.len() as u128 * 8);
+    gf_mul(y ^ lengths, h)
+}
+
+impl AESteve {
+    /// Derives the pre-counter block J0 from a nonce of any length.
+    ///
+    /// A 96-bit nonce is used directly as `nonce ‖ 0x00000001`. Any other length is
+    /// hashed, `J0 = GHASH(nonce ‖ 0-pad ‖ [0]64 ‖ [len(nonce)]64)`, which is exactly
+    /// GHASH with the nonce in place of ciphertext and no associated data.
+    fn gcm_j0(&self, nonce: &[u8]) -> [u8; 16] {
+        if let Ok(nonce) = <&[u8; 12]>::try_from(nonce) {
+            let mut j0 = [0u8; 16];
+            j0[..12].copy_from_slice(nonce);
+            j0[15] = 1;
+            return j0;
+        }
+        let h = u128::from_be_bytes(self.encrypt_block_bytes(&[0u8; 16]));
+        ghash(h, &[], nonce).to_be_bytes()
+    }
+
+    /// GCTR starting at `inc32(j0)`: the counter's low 32 bits wrap independently.
+    fn gcm_ctr(&self, j0: &[u8; 16], buffer: &mut [u8]) {
+        let prefix = u128::from_be_bytes(*j0) & !0xffff_ffffu128;
+        let start = u32::from_be_bytes([j0[12], j0[13], j0[14], j0[15]]);
+
+        buffer.par_chunks_mut(16).enumerate().for_each(|(i, chunk)| {
+            let counter = (prefix | start.wrapping_add(1).wrapping_add(i as u32) as u128).to_be_bytes();
+            let keystream = self.encrypt_block_bytes(&counter);
+            for (byte, key) in chunk.iter_mut().zip(keystream) {
+                *byte ^= key;
+            }
+        });
+    }
+
+    fn gcm_tag(&self, j0: &[u8; 16], aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+        let h = u128::from_be_bytes(self.encrypt_block_bytes(&[0u8; 16]));
+        let s = ghash(h, aad, ciphertext);
+        (s ^ u128::from_be_bytes(self.encrypt_block_bytes(j0))).to_be_bytes()
+    }
+
+    /// Encrypts `plaintext` with AES-GCM, authenticating it together with `aad`.
+    ///
+    /// The ciphertext is as long as `plaintext`, and the 128-bit tag covers both the
+    /// ciphertext and `aad`, which is authenticated but not encrypted. A nonce must never
+    /// be reused under the same key: that breaks both confidentiality and authenticity.
+    /// One message may be at most `2^32 - 2` blocks (just under 64 GiB) long, the limit
+    /// of GCM's 32-bit block counter.
+    ///
+    /// # Arguments
+    ///
+    /// * `plaintext` - The bytes to be encrypted.
+    /// * `nonce` - The 96-bit nonce, unique per message.
+    /// * `aad` - Associated data to authenticate alongside the ciphertext.
+    ///
+    /// # Returns
+    ///
+    /// * `(Vec<u8>, [u8; 16])` - The ciphertext and the authentication tag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `plaintext` is longer than `2^32 - 2` blocks.
+    pub fn encrypt_gcm(&self, plaintext: &[u8], nonce: &[u8; 12], aad: &[u8]) -> (Vec<u8>, [u8; 16]) {
+        let mut ciphertext = plaintext.to_vec();
+        let tag = self.gcm_seal_in_place(nonce, aad, &mut ciphertext);
+        (ciphertext, tag)
+    }
+
+    /// Verifies and decrypts ciphertext produced by [`AESteve::encrypt_gcm`].
+    ///
+    /// The tag is recomputed and compared in constant time, and decryption runs in full
+    /// either way, so timing reveals nothing about how close a forgery came.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The ciphertext.
+    /// * `nonce` - The nonce the ciphertext was produced with.
+    /// * `aad` - The associated data it was produced with.
+    /// * `tag` - The authentication tag.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The plaintext.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::DecryptionFailed` if the key, nonce or `aad` is wrong, the
+    /// ciphertext or tag was tampered with, or the ciphertext is longer than GCM allows.
+    pub fn decrypt_gcm(&self, ciphertext: &[u8], nonce: &[u8; 12], aad: &[u8], tag: &[u8; 16]) -> Result<Vec<u8>, AESError> {
+        let mut plaintext = ciphertext.to_vec();
+        self.gcm_open_in_place(nonce, aad, &mut plaintext, tag)?;
+        Ok(plaintext)
+    }
+
+    /// Encrypts `buffer` in place with AES-GCM and returns the authentication tag.
+    ///
+    /// Panics if `buffer` is longer than [`GCM_MAX_LEN`].
+    pub(crate) fn gcm_seal_in_place(&self, nonce: &[u8], aad: &[u8], buffer: &mut [u8]) -> [u8; 16] {
+        assert!(gcm_len_ok(buffer.len()), "AES-GCM plaintext exceeds 2^32 - 2 blocks");
+        let j0 = self.gcm_j0(nonce);
+        self.gcm_ctr(&j0, buffer);
+        self.gcm_tag(&j0, aad, buffer)
+    }
+
+    /// Verifies `tag` and decrypts `buffer` in place. `buffer` is zeroed on failure, and a
+    /// buffer longer than [`GCM_MAX_LEN`] is rejected before any work is done.
+    ///
+    /// Validity is held as a constant-time mask while the tag is checked and the buffer is
+    /// decrypted, and only turned into a `Result` once all work is done, so a forged tag
+    /// takes the same path as a genuine one.
+    pub(crate) fn gcm_open_in_place(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8; 16],
+    ) -> Result<(), AESError> {
+        if !gcm_len_ok(buffer.len()) {
+            return Err(AESError::DecryptionFailed);
+        }
+        let j0 = self.gcm_j0(nonce);
+        let valid = ct_eq_mask(&self.gcm_tag(&j0, aad, buffer), tag);
+        self.gcm_ctr(&j0, buffer);
+        ct_mask(buffer, valid);
+        if valid == 0x00 {
+            return Err(AESError::DecryptionFailed);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len()).step_by(2).map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_gcm_nist_vectors() {
+        // NIST GCM specification, test cases 4 (AES-128) and 16 (AES-256).
+        let nonce: [u8; 12] = hex("cafebabefacedbaddecaf888").try_into().unwrap();
+        let aad = hex("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+        let plaintext = hex(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+        );
+        let cases = [
+            (
+                "feffe9928665731c6d6a8f9467308308",
+                "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+                 21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+                "5bc94fbc3221a5db94fae95ae7121a47",
+            ),
+            (
+                "feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308",
+                "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa\
+                 8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662",
+                "76fc6ece0f4e1768cddf8853bb2d551b",
+            ),
+        ];
+        for (key, expected_ciphertext, expected_tag) in cases {
+            let aesteve = AESteve::new(&hex(key)).unwrap();
+            let (ciphertext, tag) = aesteve.encrypt_gcm(&plaintext, &nonce, &aad);
+            assert_eq!(ciphertext, hex(expected_ciphertext));
+            assert_eq!(tag.to_vec(), hex(expected_tag));
+            assert_eq!(aesteve.decrypt_gcm(&ciphertext, &nonce, &aad, &tag).unwrap(), plaintext);
+
+            let mut bad_tag = tag;
+            bad_tag[0] ^= 1;
+            assert!(matches!(aesteve.decrypt_gcm(&ciphertext, &nonce, &aad, &bad_tag), Err(AESError::DecryptionFailed)));
+            assert!(matches!(aesteve.decrypt_gcm(&ciphertext, &nonce, b"", &tag), Err(AESError::DecryptionFailed)));
+        }
+    }
+
+    #[test]
+    fn test_gcm_length_limit() {
+        let max_len = usize::try_from(GCM_MAX_LEN).unwrap();
+        assert!(gcm_len_ok(max_len));
+        assert!(!gcm_len_ok(max_len + 1));
+        assert_eq!(GCM_MAX_LEN / 16, u32::MAX as u64 - 1);
+    }
+
+    #[test]
+    fn test_gcm_failures_indistinguishable() {
+        let aesteve = AESteve::new(&[9u8; 16]).unwrap();
+        let nonce = [3u8; 12];
+        let mut ciphertext = *b"authenticated body";
+        let tag = aesteve.gcm_seal_in_place(&nonce, b"header", &mut ciphertext);
+
+        let mut bad_tag = tag;
+        bad_tag[15] ^= 1;
+        let mut bad_body = ciphertext;
+        bad_body[0] ^= 1;
+        let failures = [
+            aesteve.gcm_open_in_place(&nonce, b"header", &mut ciphertext.clone(), &bad_tag),
+            aesteve.gcm_open_in_place(&nonce, b"header", &mut bad_body.clone(), &tag),
+            aesteve.gcm_open_in_place(&nonce, b"header", &mut ciphertext[..17].to_vec(), &tag),
+            aesteve.gcm_open_in_place(&nonce, b"other", &mut ciphertext.clone(), &tag),
+        ];
+        for failure in failures {
+            assert!(matches!(failure, Err(AESError::DecryptionFailed)));
+        }
+
+        let mut buffer = ciphertext;
+        aesteve.gcm_open_in_place(&nonce, b"header", &mut buffer, &tag).unwrap();
+        assert_eq!(&buffer, b"authenticated body");
+    }
+
+    #[test]
+    fn test_gcm_open_does_full_work_on_failure() {
+        let aesteve = AESteve::new(&[9u8; 16]).unwrap();
+        let nonce = [3u8; 12];
+        let mut ciphertext = *b"authenticated body";
+        let mut tag = aesteve.gcm_seal_in_place(&nonce, b"header", &mut ciphertext);
+        tag[0] ^= 1;
+
+        // A forged tag is not rejected before decryption: the buffer goes through the same
+        // decryption and masking as a genuine one, ending up zeroed instead of plaintext.
+        let mut buffer = ciphertext;
+        assert!(aesteve.gcm_open_in_place(&nonce, b"header", &mut buffer, &tag).is_err());
+        assert_eq!(buffer, [0u8; 18]);
+    }
+}
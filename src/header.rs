@@ -0,0 +1,99 @@
+use crate::{random_array, AESError, AESteve};
+
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+impl AESteve {
+    /// Encrypts `body` with AES-GCM at this instance's key size, leaving `header` in the clear but authenticated.
+    ///
+    /// For message formats with a plaintext routing header: the output is
+    /// `header ‖ nonce ‖ ciphertext ‖ tag`, with `header` passed to GCM as associated data,
+    /// so intermediaries can read it but not change it. The nonce is random.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The bytes to send in the clear.
+    /// * `body` - The bytes to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The sealed message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system's random number generator is unavailable, or if
+    /// `body` is longer than AES-GCM allows (`2^32 - 2` blocks).
+    pub fn encrypt_with_header(&self, header: &[u8], body: &[u8]) -> Vec<u8> {
+        let nonce: [u8; NONCE_LEN] = random_array();
+        let mut sealed = Vec::with_capacity(header.len() + NONCE_LEN + body.len() + TAG_LEN);
+        sealed.extend_from_slice(header);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(body);
+
+        let (header, ciphertext) = sealed.split_at_mut(header.len() + NONCE_LEN);
+        let tag = self.gcm_seal_in_place(&nonce, &header[..header.len() - NONCE_LEN], ciphertext);
+        sealed.extend_from_slice(&tag);
+        sealed
+    }
+
+    /// Decrypts a message produced by [`AESteve::encrypt_with_header`], verifying that
+    /// neither the header nor the body was tampered with.
+    ///
+    /// The header is `sealed[..header_len]`; read it from there once this returns `Ok`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sealed` - The sealed message.
+    /// * `header_len` - The length of the cleartext header, as fixed by the message format.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted body.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::DecryptionFailed` if the key or `header_len` is wrong, or the
+    /// message is truncated or tampered with.
+    pub fn decrypt_with_header(&self, sealed: &[u8], header_len: usize) -> Result<Vec<u8>, AESError> {
+        if sealed.len() < header_len.saturating_add(NONCE_LEN + TAG_LEN) {
+            return Err(AESError::DecryptionFailed);
+        }
+        let (header, body) = sealed.split_at(header_len);
+        let (nonce, body) = body.split_at(NONCE_LEN);
+        let (ciphertext, tag) = body.split_at(body.len() - TAG_LEN);
+
+        let mut plaintext = ciphertext.to_vec();
+        self.gcm_open_in_place(nonce, header, &mut plaintext, tag.try_into().unwrap())?;
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_round_trip() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let sealed = aesteve.encrypt_with_header(b"route:eu-west", b"the body");
+        assert_eq!(&sealed[..13], b"route:eu-west");
+        assert_eq!(sealed.len(), 13 + NONCE_LEN + 8 + TAG_LEN);
+        assert_eq!(aesteve.decrypt_with_header(&sealed, 13).unwrap(), b"the body");
+
+        let empty = aesteve.encrypt_with_header(b"", b"");
+        assert_eq!(aesteve.decrypt_with_header(&empty, 0).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_header_tampering_fails() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let sealed = aesteve.encrypt_with_header(b"route:eu-west", b"the body");
+        for i in [0, 12, 13, sealed.len() - 1] {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert!(matches!(aesteve.decrypt_with_header(&tampered, 13), Err(AESError::DecryptionFailed)));
+        }
+        assert!(matches!(aesteve.decrypt_with_header(&sealed, 12), Err(AESError::DecryptionFailed)));
+        assert!(matches!(aesteve.decrypt_with_header(&sealed, usize::MAX), Err(AESError::DecryptionFailed)));
+    }
+}
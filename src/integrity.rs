@@ -0,0 +1,58 @@
+use crate::ct::ct_eq;
+use crate::{wipe, AESError, AESteve};
+use sha2::{Digest, Sha256};
+
+impl AESteve {
+    /// Decrypts the given ECB-encrypted message and checks its SHA-256 against a hash
+    /// known out-of-band.
+    ///
+    /// For formats that cannot carry a MAC. The hashes are compared in constant time,
+    /// and the plaintext is zeroed before an error is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted_message` - The encrypted message in the configured encoding.
+    /// * `expected_sha256` - The SHA-256 digest the plaintext must have.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The decrypted message.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::IntegrityCheckFailed` if the plaintext does not hash to
+    /// `expected_sha256`, or any error `decrypt_ecb` returns.
+    pub fn decrypt_verify_hash(&self, encrypted_message: String, expected_sha256: [u8; 32]) -> Result<String, AESError> {
+        let mut plaintext = self.decrypt_ecb(encrypted_message)?;
+        if !ct_eq(&Sha256::digest(plaintext.as_bytes()), &expected_sha256) {
+            // SAFETY: zeroes are valid UTF-8.
+            wipe(unsafe { plaintext.as_bytes_mut() });
+            return Err(AESError::IntegrityCheckFailed);
+        }
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decrypt_verify_hash() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let encrypted_message = aesteve.encrypt_ecb(String::from("abc")).unwrap();
+        // FIPS 180-2, appendix B.1.
+        let expected = [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23,
+            0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+        ];
+        assert_eq!(aesteve.decrypt_verify_hash(encrypted_message.clone(), expected).unwrap(), "abc");
+
+        let mut wrong = expected;
+        wrong[31] ^= 1;
+        assert!(matches!(
+            aesteve.decrypt_verify_hash(encrypted_message, wrong),
+            Err(AESError::IntegrityCheckFailed)
+        ));
+    }
+}
@@ -0,0 +1,57 @@
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+const AES_KEY_LABEL: &[u8] = b"aesteve/aes-key/";
+const MAC_KEY_LABEL: &[u8] = b"aesteve/mac-key/";
+
+/// Derives an independent AES key and MAC key from a single master key.
+///
+/// Both keys come from HKDF-SHA256 over `master`, expanded with distinct labels
+/// prepended to `info`, so neither key reveals anything about the other.
+///
+/// # Arguments
+///
+/// * `master` - The master key material.
+/// * `info` - Context binding the derived keys to a purpose (e.g. a protocol name).
+///
+/// # Returns
+///
+/// * `([u8; 16], [u8; 32])` - The AES-128 key and the 256-bit MAC key.
+pub fn derive_keys(master: &[u8], info: &[u8]) -> ([u8; 16], [u8; 32]) {
+    expand_keys(&Hkdf::new(None, master), info)
+}
+
+/// Like [`derive_keys`], but with `salt` as the HKDF salt.
+#[cfg(feature = "auth")]
+pub(crate) fn derive_salted_keys(salt: &[u8], master: &[u8], info: &[u8]) -> ([u8; 16], [u8; 32]) {
+    expand_keys(&Hkdf::new(Some(salt), master), info)
+}
+
+fn expand_keys(hkdf: &Hkdf<Sha256>, info: &[u8]) -> ([u8; 16], [u8; 32]) {
+    let mut aes_key = [0u8; 16];
+    hkdf.expand_multi_info(&[AES_KEY_LABEL, info], &mut aes_key)
+        .expect("16 bytes is a valid HKDF-SHA256 output length");
+
+    let mut mac_key = [0u8; 32];
+    hkdf.expand_multi_info(&[MAC_KEY_LABEL, info], &mut mac_key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+
+    (aes_key, mac_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_keys_independent() {
+        let (aes_key, mac_key) = derive_keys(b"master key", b"app v1");
+        assert_ne!(aes_key[..], mac_key[..16]);
+    }
+
+    #[test]
+    fn test_derive_keys_deterministic() {
+        assert_eq!(derive_keys(b"master key", b"app v1"), derive_keys(b"master key", b"app v1"));
+        assert_ne!(derive_keys(b"master key", b"app v1"), derive_keys(b"master key", b"app v2"));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{lookup, round_constant, wipe, AESError};
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+/// The expanded AES key schedule: `4 * (rounds + 1)` words, read four at a time as round
+/// keys, plus other key-derived values cached on first use, all zeroed when dropped.
+///
+/// Never mutated after expansion apart from filling the caches, which `OnceLock` makes
+/// thread-safe, so one schedule can be shared freely between threads behind an `Arc`.
+#[derive(Debug)]
+pub(crate) struct KeySchedule {
+    words: Vec<[u8; 4]>,
+    /// The number of rounds, `Nr = Nk + 6`: 10, 12 or 14 for AES-128, -192 or -256.
+    pub(crate) rounds: usize,
+    /// The CMAC subkeys K1 and K2, derived on the first `cmac` call.
+    pub(crate) cmac_subkeys: OnceLock<([u8; 16], [u8; 16])>,
+}
+
+impl KeySchedule {
+    /// Expands a key of `nk` 32-bit words (4, 6 or 8) as in FIPS-197, section 5.2.
+    ///
+    /// Every `nk`-th word goes through RotWord, SubWord and the next round constant, so
+    /// longer keys use fewer round constants rather than more; for `nk > 6` the word four
+    /// after it goes through SubWord as well. Fails only if a round constant lookup reports
+    /// an internal error under `strict-errors`.
+    pub(crate) fn expand(key: &[u8], nk: usize) -> Result<Self, AESError> {
+        debug_assert_eq!(key.len(), 4 * nk);
+        let rounds = nk + 6;
+        let mut words: Vec<[u8; 4]> = Vec::with_capacity(4 * (rounds + 1));
+        words.extend(key.as_chunks::<4>().0);
+
+        for i in nk..4 * (rounds + 1) {
+            let previous = words[i - 1];
+            let temp = if i % nk == 0 {
+                [
+                    lookup(previous[1]) ^ round_constant((i / nk - 1) as u8)?,
+                    lookup(previous[2]),
+                    lookup(previous[3]),
+                    lookup(previous[0]),
+                ]
+            } else if nk > 6 && i % nk == 4 {
+                previous.map(lookup)
+            } else {
+                previous
+            };
+            let back = words[i - nk];
+            words.push([back[0] ^ temp[0], back[1] ^ temp[1], back[2] ^ temp[2], back[3] ^ temp[3]]);
+        }
+        Ok(KeySchedule { words, rounds, cmac_subkeys: OnceLock::new() })
+    }
+
+    fn wipe(&mut self) {
+        wipe(self.words.as_flattened_mut());
+        if let Some((k1, k2)) = self.cmac_subkeys.get_mut() {
+            wipe(k1);
+            wipe(k2);
+        }
+    }
+}
+
+impl Deref for KeySchedule {
+    type Target = [[[u8; 4]; 4]];
+
+    fn deref(&self) -> &Self::Target {
+        self.words.as_chunks::<4>().0
+    }
+}
+
+impl Drop for KeySchedule {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wipe() {
+        let mut schedule = KeySchedule::expand(&[0x42u8; 16], 4).unwrap();
+        schedule.cmac_subkeys.set(([0x42u8; 16], [0x42u8; 16])).unwrap();
+        assert_ne!(*schedule, [[[0u8; 4]; 4]; 11]);
+        schedule.wipe();
+        assert_eq!(*schedule, [[[0u8; 4]; 4]; 11]);
+        assert_eq!(schedule.cmac_subkeys.get(), Some(&([0u8; 16], [0u8; 16])));
+    }
+
+    #[test]
+    fn test_aes192_expansion() {
+        // FIPS-197, appendix A.2.
+        let key = [
+            0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 0xc8, 0x10, 0xf3, 0x2b,
+            0x80, 0x90, 0x79, 0xe5, 0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b,
+        ];
+        let schedule = KeySchedule::expand(&key, key.len() / 4).unwrap();
+        assert_eq!(schedule.rounds, 12);
+        assert_eq!(schedule.len(), 13);
+        assert_eq!(schedule[1][2], [0xfe, 0x0c, 0x91, 0xf7]);
+        assert_eq!(schedule[12][3], [0x01, 0x00, 0x22, 0x02]);
+    }
+
+    #[test]
+    fn test_aes256_expansion() {
+        // FIPS-197, appendix A.3.
+        let key = [
+            0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77, 0x81,
+            0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14, 0xdf, 0xf4,
+        ];
+        let schedule = KeySchedule::expand(&key, key.len() / 4).unwrap();
+        assert_eq!(schedule.rounds, 14);
+        assert_eq!(schedule.len(), 15);
+        assert_eq!(schedule[2][0], [0x9b, 0xa3, 0x54, 0x11]);
+        assert_eq!(schedule[3][0], [0xa8, 0xb0, 0x9c, 0x1a]);
+        assert_eq!(schedule[14], [[0xfe, 0x48, 0x90, 0xd1], [0xe6, 0x18, 0x8d, 0x0b], [0x04, 0x6d, 0xf3, 0x44], [0x70, 0x6c, 0x63, 0x1e]]);
+    }
+}
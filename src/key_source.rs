@@ -0,0 +1,112 @@
+use crate::{AESError, AESteve, Encoding};
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+impl AESteve {
+    /// Creates a new AES instance from a key stored in an environment variable.
+    ///
+    /// The value may be hex or standard Base64. Values made up only of hex digits
+    /// are decoded as hex; anything else is decoded as Base64. Surrounding
+    /// whitespace is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `var_name` - The name of the environment variable holding the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::KeySourceMissing` if the variable is unset or not valid Unicode,
+    /// `AESError::InvalidBase64` if the value is neither hex nor Base64, and
+    /// `AESError::InvalidKeyLength` if the decoded key is not 16, 24 or 32 bytes.
+    pub fn from_env(var_name: &str) -> Result<Self, AESError> {
+        let value = std::env::var(var_name).map_err(|_| AESError::KeySourceMissing(var_name.to_string()))?;
+        Self::from_key_text(&value)
+    }
+
+    /// Decodes a hex or Base64 key the way [`AESteve::from_env`] does, so tests can
+    /// exercise the parsing without touching the process environment.
+    fn from_key_text(value: &str) -> Result<Self, AESError> {
+        let value = value.trim();
+        let key = match decode_hex(value) {
+            Some(key) => key,
+            None => Encoding::Base64.decode(value)?,
+        };
+        Self::new(&key)
+    }
+
+    /// Creates a new AES instance from a 12-word BIP39 mnemonic.
+    ///
+    /// The 128 bits of entropy a 12-word phrase encodes are used directly as the key,
+    /// so every key has exactly one phrase and converting back and forth is lossless.
+    /// The phrase is Unicode-normalized, and its checksum word is verified.
+    ///
+    /// # Arguments
+    ///
+    /// * `phrase` - The space-separated English mnemonic.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidMnemonic` if the phrase contains an unknown word, has a
+    /// bad checksum, or is not exactly 12 words long.
+    #[cfg(feature = "mnemonic")]
+    pub fn from_mnemonic(phrase: &str) -> Result<Self, AESError> {
+        let mnemonic = bip39::Mnemonic::parse(phrase).map_err(|_| AESError::InvalidMnemonic)?;
+        let (mut entropy, len) = mnemonic.to_entropy_array();
+        let result = if len == 16 { Self::new(&entropy[..len]) } else { Err(AESError::InvalidMnemonic) };
+        crate::wipe(&mut entropy);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Setting environment variables races with the other test threads reading them, so
+    // these go through `from_key_text`; tests/key_source_env.rs covers a set variable.
+    #[test]
+    fn test_from_env_hex_and_base64() {
+        let reference = AESteve::new(&(0u8..16).collect::<Vec<u8>>()).unwrap();
+        let encrypted_message = reference.encrypt_ecb(String::from("twelve-factor")).unwrap();
+
+        let from_hex = AESteve::from_key_text("000102030405060708090a0b0c0d0e0f").unwrap();
+        assert_eq!(from_hex.decrypt_ecb(encrypted_message.clone()).unwrap(), "twelve-factor");
+
+        let from_base64 = AESteve::from_key_text("AAECAwQFBgcICQoLDA0ODw==\n").unwrap();
+        assert_eq!(from_base64.decrypt_ecb(encrypted_message).unwrap(), "twelve-factor");
+    }
+
+    #[test]
+    fn test_from_env_errors() {
+        assert!(matches!(
+            AESteve::from_env("AESTEVE_TEST_UNSET_KEY"),
+            Err(AESError::KeySourceMissing(name)) if name == "AESTEVE_TEST_UNSET_KEY"
+        ));
+        assert!(matches!(AESteve::from_key_text("not a key!"), Err(AESError::InvalidBase64(_))));
+        assert!(matches!(AESteve::from_key_text("0001020304"), Err(AESError::InvalidKeyLength)));
+    }
+
+    #[test]
+    #[cfg(feature = "mnemonic")]
+    fn test_from_mnemonic() {
+        // BIP39 test vector for entropy 7f7f...7f.
+        let phrase = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        let from_phrase = AESteve::from_mnemonic(phrase).unwrap();
+        let reference = AESteve::new(&[0x7f; 16]).unwrap();
+        assert_eq!(from_phrase.encrypt_array([0u8; 16]), reference.encrypt_array([0u8; 16]));
+
+        let bad_checksum = "legal winner thank year wave sausage worth useful legal winner thank thank";
+        assert!(matches!(AESteve::from_mnemonic(bad_checksum), Err(AESError::InvalidMnemonic)));
+        assert!(matches!(AESteve::from_mnemonic("not a mnemonic"), Err(AESError::InvalidMnemonic)));
+        let twenty_four = ["abandon"; 23].join(" ") + " art";
+        assert!(matches!(AESteve::from_mnemonic(&twenty_four), Err(AESError::InvalidMnemonic)));
+    }
+}
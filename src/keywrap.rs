@@ -0,0 +1,159 @@
+use crate::ct::ct_eq;
+use crate::{wipe, AESError, AESteve};
+
+/// The RFC 3394 default initial value, checked on unwrap as the integrity check.
+const DEFAULT_IV: [u8; 8] = [0xA6; 8];
+
+impl AESteve {
+    /// Wraps key material with the RFC 3394 AES key wrap algorithm.
+    ///
+    /// The cipher's key is the key-encryption key. Wrapping is deterministic and adds
+    /// one 8-byte integrity block, so the output is 8 bytes longer than `key_data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_data` - The key material to wrap: at least 16 bytes, in 8-byte units.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<u8>, AESError>` - The wrapped key or an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidDataLength` if `key_data` is shorter than 16 bytes or
+    /// not a multiple of 8 bytes.
+    pub fn wrap_key(&self, key_data: &[u8]) -> Result<Vec<u8>, AESError> {
+        if key_data.len() < 16 || !key_data.len().is_multiple_of(8) {
+            return Err(AESError::InvalidDataLength);
+        }
+
+        let n = key_data.len() / 8;
+        let mut wrapped = Vec::with_capacity(key_data.len() + 8);
+        wrapped.extend_from_slice(&DEFAULT_IV);
+        wrapped.extend_from_slice(key_data);
+
+        let mut block = [0u8; 16];
+        for j in 0..6 {
+            for i in 1..=n {
+                block[..8].copy_from_slice(&wrapped[..8]);
+                block[8..].copy_from_slice(&wrapped[i * 8..(i + 1) * 8]);
+                block = self.encrypt_block_bytes(&block);
+                let t = (n * j + i) as u64;
+                for (a, t) in block[..8].iter_mut().zip(t.to_be_bytes()) {
+                    *a ^= t;
+                }
+                wrapped[..8].copy_from_slice(&block[..8]);
+                wrapped[i * 8..(i + 1) * 8].copy_from_slice(&block[8..]);
+            }
+        }
+        wipe(&mut block);
+        Ok(wrapped)
+    }
+
+    /// Unwraps key material produced by [`AESteve::wrap_key`] under the same key.
+    ///
+    /// # Arguments
+    ///
+    /// * `wrapped` - The wrapped key: at least 24 bytes, in 8-byte units.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<u8>, AESError>` - The unwrapped key material or an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidDataLength` if `wrapped` is shorter than 24 bytes or not
+    /// a multiple of 8 bytes, and `AESError::KeyUnwrapFailed` if the integrity check
+    /// fails because the data was altered or wrapped under a different key. No key
+    /// material is returned on failure.
+    pub fn unwrap_key(&self, wrapped: &[u8]) -> Result<Vec<u8>, AESError> {
+        if wrapped.len() < 24 || !wrapped.len().is_multiple_of(8) {
+            return Err(AESError::InvalidDataLength);
+        }
+
+        let n = wrapped.len() / 8 - 1;
+        let mut a: [u8; 8] = wrapped[..8].try_into().unwrap();
+        let mut key_data = wrapped[8..].to_vec();
+
+        let mut block = [0u8; 16];
+        for j in (0..6).rev() {
+            for i in (1..=n).rev() {
+                let t = (n * j + i) as u64;
+                for ((b, a), t) in block[..8].iter_mut().zip(a).zip(t.to_be_bytes()) {
+                    *b = a ^ t;
+                }
+                block[8..].copy_from_slice(&key_data[(i - 1) * 8..i * 8]);
+                block = self.decrypt_block_bytes(&block);
+                a.copy_from_slice(&block[..8]);
+                key_data[(i - 1) * 8..i * 8].copy_from_slice(&block[8..]);
+            }
+        }
+        wipe(&mut block);
+
+        if !ct_eq(&a, &DEFAULT_IV) {
+            wipe(&mut key_data);
+            return Err(AESError::KeyUnwrapFailed);
+        }
+        Ok(key_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_wrap_rfc3394_128() {
+        // RFC 3394, section 4.1: 128 bits of key data with a 128-bit KEK.
+        let kek: Vec<u8> = (0u8..16).collect();
+        let key_data = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+        ];
+        let expected = [
+            0x1f, 0xa6, 0x8b, 0x0a, 0x81, 0x12, 0xb4, 0x47, 0xae, 0xf3, 0x4b, 0xd8, 0xfb, 0x5a, 0x7b, 0x82,
+            0x9d, 0x3e, 0x86, 0x23, 0x71, 0xd2, 0xcf, 0xe5,
+        ];
+        let aesteve = AESteve::new(&kek).unwrap();
+        assert_eq!(aesteve.wrap_key(&key_data).unwrap(), expected);
+        assert_eq!(aesteve.unwrap_key(&expected).unwrap(), key_data);
+    }
+
+    #[test]
+    fn test_key_wrap_rfc3394_256() {
+        // RFC 3394, section 4.6: 256 bits of key data with a 256-bit KEK.
+        let kek: Vec<u8> = (0u8..32).collect();
+        let key_data = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+        ];
+        let expected = [
+            0x28, 0xc9, 0xf4, 0x04, 0xc4, 0xb8, 0x10, 0xf4, 0xcb, 0xcc, 0xb3, 0x5c, 0xfb, 0x87, 0xf8, 0x26,
+            0x3f, 0x57, 0x86, 0xe2, 0xd8, 0x0e, 0xd3, 0x26, 0xcb, 0xc7, 0xf0, 0xe7, 0x1a, 0x99, 0xf4, 0x3b,
+            0xfb, 0x98, 0x8b, 0x9b, 0x7a, 0x02, 0xdd, 0x21,
+        ];
+        let aesteve = AESteve::new(&kek).unwrap();
+        assert_eq!(aesteve.wrap_key(&key_data).unwrap(), expected);
+        assert_eq!(aesteve.unwrap_key(&expected).unwrap(), key_data);
+    }
+
+    #[test]
+    fn test_unwrap_key_detects_tampering() {
+        let aesteve = AESteve::new(&[0x5Au8; 16]).unwrap();
+        let mut wrapped = aesteve.wrap_key(&[0x42u8; 24]).unwrap();
+        wrapped[12] ^= 0x01;
+        assert!(matches!(aesteve.unwrap_key(&wrapped), Err(AESError::KeyUnwrapFailed)));
+
+        let other = AESteve::new(&[0xA5u8; 16]).unwrap();
+        let wrapped = aesteve.wrap_key(&[0x42u8; 24]).unwrap();
+        assert!(matches!(other.unwrap_key(&wrapped), Err(AESError::KeyUnwrapFailed)));
+    }
+
+    #[test]
+    fn test_key_wrap_rejects_bad_lengths() {
+        let aesteve = AESteve::new(&[0x5Au8; 16]).unwrap();
+        assert!(matches!(aesteve.wrap_key(&[0u8; 8]), Err(AESError::InvalidDataLength)));
+        assert!(matches!(aesteve.wrap_key(&[0u8; 20]), Err(AESError::InvalidDataLength)));
+        assert!(matches!(aesteve.unwrap_key(&[0u8; 16]), Err(AESError::InvalidDataLength)));
+        assert!(matches!(aesteve.unwrap_key(&[0u8; 28]), Err(AESError::InvalidDataLength)));
+    }
+}
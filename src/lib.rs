@@ -1,6 +1,90 @@
-use base64::{Engine, engine::general_purpose::STANDARD};
+use key_schedule::KeySchedule;
 use rayon::prelude::*;
+use sbox::SBox;
 use std::fmt;
+use std::sync::Arc;
+
+#[cfg(feature = "aead")]
+mod aead;
+mod analysis;
+#[cfg(feature = "auth")]
+mod auth;
+mod batch;
+#[cfg(feature = "bench-api")]
+mod bench_api;
+mod cbc;
+mod cfb;
+mod chain;
+#[cfg(feature = "experimental")]
+mod blocked;
+mod checksum;
+mod cmac;
+mod ct;
+mod ctr;
+mod ctr_stream;
+mod encoding;
+mod envelope;
+mod fields;
+#[cfg(feature = "fingerprint")]
+mod fingerprint;
+mod framing;
+mod gcm;
+mod header;
+#[cfg(feature = "integrity")]
+mod integrity;
+mod key_schedule;
+mod key_source;
+mod keywrap;
+#[cfg(feature = "kdf")]
+mod kdf;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod mode;
+mod multi_block;
+mod ofb;
+mod oneshot;
+mod padding;
+mod reversed;
+mod rotate;
+mod sbox;
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(feature = "password")]
+mod secretbox;
+mod self_test;
+mod siv;
+mod smart;
+mod stats;
+#[cfg(feature = "serde")]
+mod value;
+mod xts;
+
+#[cfg(feature = "aead")]
+pub use aead::AESteveGcm;
+pub use analysis::looks_like_ecb;
+#[cfg(feature = "auth")]
+pub use auth::AuthCipher;
+#[cfg(feature = "bench-api")]
+pub use bench_api::{compare_decrypt_paths, expand_key_nanos};
+pub use ct::ct_select;
+pub use ctr_stream::{CtrReader, CtrWriter};
+#[cfg(feature = "async")]
+pub use ctr_stream::CtrStream;
+#[cfg(feature = "kdf")]
+pub use kdf::derive_keys;
+pub use encoding::{reencode, Encoding};
+pub use envelope::{detect_variant, AesVariant};
+#[cfg(feature = "fingerprint")]
+pub use fingerprint::ciphertext_fingerprint;
+pub use framing::plaintext_len;
+pub use oneshot::{aes128_ecb_decrypt, aes128_ecb_encrypt};
+pub use mode::{CipherMode, Mode};
+pub use padding::{Padding, PaddingScheme};
+#[cfg(feature = "zeroize")]
+pub use secret::Secret;
+#[cfg(feature = "password")]
+pub use secretbox::{open_with_password, seal_with_password};
+pub use stats::EncryptStats;
 
 /// Possible errors for AES operations.
 #[derive(Debug)]
@@ -8,6 +92,27 @@ pub enum AESError {
     InvalidBase64(base64::DecodeError),
     InvalidUTF8(std::string::FromUtf8Error),
     InvalidKeyLength,
+    InvalidPadding,
+    KeySourceMissing(String),
+    InvalidPlaintextLength,
+    DecryptionFailed,
+    CiphertextTooShort,
+    InvalidCiphertextLength,
+    InvalidDataLength,
+    ChecksumMismatch,
+    CiphertextTooLarge,
+    BadMagic,
+    UnsupportedVersion,
+    InvalidMnemonic,
+    IntegrityCheckFailed,
+    SelfTestFailed,
+    KeyUnwrapFailed,
+    InternalError(String),
+    InvalidSBox,
+    #[cfg(feature = "mmap")]
+    Io(std::io::Error),
+    #[cfg(feature = "serde")]
+    Serialization(serde_json::Error),
 }
 
 impl fmt::Display for AESError {
@@ -16,6 +121,27 @@ impl fmt::Display for AESError {
             AESError::InvalidBase64(err) => write!(f, "Base64 decoding error: {}", err),
             AESError::InvalidUTF8(err) => write!(f, "UTF-8 decoding error: {}", err),
             AESError::InvalidKeyLength => write!(f, "Invalid key length"),
+            AESError::InvalidPadding => write!(f, "Invalid padding"),
+            AESError::KeySourceMissing(name) => write!(f, "Key source missing: {}", name),
+            AESError::InvalidPlaintextLength => write!(f, "Plaintext length does not fit the data"),
+            AESError::DecryptionFailed => write!(f, "Decryption failed"),
+            AESError::CiphertextTooShort => write!(f, "Ciphertext shorter than one block"),
+            AESError::InvalidCiphertextLength => write!(f, "Ciphertext length is not a multiple of the block size"),
+            AESError::InvalidDataLength => write!(f, "Invalid data length for this mode"),
+            AESError::ChecksumMismatch => write!(f, "Plaintext checksum mismatch"),
+            AESError::CiphertextTooLarge => write!(f, "Ciphertext exceeds the configured maximum length"),
+            AESError::BadMagic => write!(f, "Data is not an AESteve envelope"),
+            AESError::UnsupportedVersion => write!(f, "Unsupported envelope version"),
+            AESError::InvalidMnemonic => write!(f, "Invalid mnemonic phrase"),
+            AESError::IntegrityCheckFailed => write!(f, "Plaintext does not match the expected hash"),
+            AESError::SelfTestFailed => write!(f, "Cipher self-test failed"),
+            AESError::KeyUnwrapFailed => write!(f, "Wrapped key failed its integrity check"),
+            AESError::InternalError(what) => write!(f, "Internal error: {}", what),
+            AESError::InvalidSBox => write!(f, "Inverse S-box does not invert the S-box"),
+            #[cfg(feature = "mmap")]
+            AESError::Io(err) => write!(f, "I/O error: {}", err),
+            #[cfg(feature = "serde")]
+            AESError::Serialization(err) => write!(f, "Serialization error: {}", err),
         }
     }
 }
@@ -34,6 +160,20 @@ impl From<std::string::FromUtf8Error> for AESError {
     }
 }
 
+#[cfg(feature = "mmap")]
+impl From<std::io::Error> for AESError {
+    fn from(err: std::io::Error) -> Self {
+        AESError::Io(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for AESError {
+    fn from(err: serde_json::Error) -> Self {
+        AESError::Serialization(err)
+    }
+}
+
 /// Lookup Tables and Lookup Functions
 static TABLE: [[u8; 256]; 6]  = [
     [0x00,0x02,0x04,0x06,0x08,0x0a,0x0c,0x0e,0x10,0x12,0x14,0x16,0x18,0x1a,0x1c,0x1e,
@@ -133,7 +273,7 @@ static TABLE: [[u8; 256]; 6]  = [
         0x37,0x39,0x2b,0x25,0x0f,0x01,0x13,0x1d,0x47,0x49,0x5b,0x55,0x7f,0x71,0x63,0x6d,
         0xd7,0xd9,0xcb,0xc5,0xef,0xe1,0xf3,0xfd,0xa7,0xa9,0xbb,0xb5,0x9f,0x91,0x83,0x8d]];
 
-static AES_SBOX: [[u8;16];16] = [[99, 124, 119, 123, 242, 107, 111, 197, 48, 1, 103, 43, 254, 215, 171, 118],
+const AES_SBOX: [[u8;16];16] = [[99, 124, 119, 123, 242, 107, 111, 197, 48, 1, 103, 43, 254, 215, 171, 118],
     [202, 130, 201, 125, 250, 89, 71, 240, 173, 212, 162, 175, 156, 164, 114, 192],
     [183, 253, 147, 38, 54, 63, 247, 204, 52, 165, 229, 241, 113, 216, 49, 21],
     [4, 199, 35, 195, 24, 150, 5, 154, 7, 18, 128, 226, 235, 39, 178, 117],
@@ -150,7 +290,7 @@ static AES_SBOX: [[u8;16];16] = [[99, 124, 119, 123, 242, 107, 111, 197, 48, 1,
 [225, 248, 152, 17, 105, 217, 142, 148, 155, 30, 135, 233, 206, 85, 40, 223],
 [140, 161, 137, 13, 191, 230, 66, 104, 65, 153, 45, 15, 176, 84, 187, 22]];
 
-static REVERSE_AES_SBOX: [[u8;16];16] = [[82, 9, 106, 213, 48, 54, 165, 56, 191, 64, 163, 158, 129, 243, 215, 251],
+const REVERSE_AES_SBOX: [[u8;16];16] = [[82, 9, 106, 213, 48, 54, 165, 56, 191, 64, 163, 158, 129, 243, 215, 251],
     [124, 227, 57, 130, 155, 47, 255, 135, 52, 142, 67, 68, 196, 222, 233, 203],
     [84, 123, 148, 50, 166, 194, 35, 61, 238, 76, 149, 11, 66, 250, 195, 78],
     [8, 46, 161, 102, 40, 217, 36, 178, 118, 91, 162, 73, 109, 139, 209, 37],
@@ -182,139 +322,307 @@ static INV_MIX_MATRIX:[[u8;4];4]= [
 static RC: [u8;22] = [0x01,0x02,0x04,0x08,0x10,0x20,0x40,0x80,0x1B,0x36,0x6C,0xDB,0xAB,0x4D,0x9A,0x2F,0x5E,0xBC,0x63,0xC6,0x97,0x35];
 
 
-fn lookup(byte: u8) -> u8 {
-    let x = (byte >> 4) as usize;
-    let y = (byte & 15) as usize;
+/// Lays a 16x16 S-box out row by row, so byte `b` is at index `b` rather than `[b >> 4][b & 15]`.
+const fn flatten(table: &[[u8; 16]; 16]) -> [u8; 256] {
+    let mut flat = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        flat[i] = table[i >> 4][i & 15];
+        i += 1;
+    }
+    flat
+}
+
+static SBOX_FLAT: [u8; 256] = flatten(&AES_SBOX);
+static REVERSE_SBOX_FLAT: [u8; 256] = flatten(&REVERSE_AES_SBOX);
 
-    AES_SBOX.get(x)
-        .and_then(|row| row.get(y))
-        .copied()
-        .unwrap_or_else(|| panic!("Invalid index in AES_SBOX for byte: {}", byte))
+const fn lookup(byte: u8) -> u8 {
+    SBOX_FLAT[byte as usize]
 }
 
+const fn reverse_lookup(byte: u8) -> u8 {
+    REVERSE_SBOX_FLAT[byte as usize]
+}
+/// Multiplies by `x` in GF(2^8) without a table lookup or a data-dependent branch.
+pub(crate) fn xtime(byte: u8) -> u8 {
+    (byte << 1) ^ (0x1b & 0u8.wrapping_sub(byte >> 7))
+}
 
-fn reverse_lookup(byte: u8) -> u8 {
-    let x = (byte >> 4) as usize;
-    let y = (byte & 15) as usize;
+/// Multiplies `m` by `n` in GF(2^8) by shift-and-add. Branches only on `n`, which is
+/// always a public constant.
+#[cfg(test)]
+fn gf_mul_slow(n: u8, m: u8) -> u8 {
+    let (mut n, mut m, mut product) = (n, m, 0u8);
+    while n != 0 {
+        if n & 1 != 0 {
+            product ^= m;
+        }
+        m = xtime(m);
+        n >>= 1;
+    }
+    product
+}
 
-    REVERSE_AES_SBOX.get(x)
-        .and_then(|row| row.get(y))
-        .copied()
-        .unwrap_or_else(|| panic!("Invalid index in REVERSE_AES_SBOX for byte: {}", byte))
+/// Reports an internal invariant violation under the configured policy: a panic with
+/// `debug-panics`, otherwise `AESError::InternalError` with the default `strict-errors`.
+///
+/// `debug-panics` takes precedence, so enabling it on top of the defaults is enough. With
+/// neither feature nothing is reported and the caller computes the exact value the slow way.
+fn internal_error(what: fmt::Arguments<'_>) -> Result<(), AESError> {
+    if cfg!(feature = "debug-panics") {
+        panic!("{}", what);
+    }
+    if cfg!(feature = "strict-errors") {
+        return Err(AESError::InternalError(what.to_string()));
+    }
+    Ok(())
 }
-fn round_constant(round:u8)->u8{
-    RC.get(round as usize).copied().unwrap_or_else(|| panic!("Invalid round: {}", round))
+
+fn round_constant(round: u8) -> Result<u8, AESError> {
+    match RC.get(round as usize) {
+        Some(&rc) => Ok(rc),
+        None => {
+            internal_error(format_args!("Invalid round: {}", round))?;
+            Ok((0..round).fold(0x01, |rc, _| xtime(rc)))
+        }
+    }
 }
 
-fn table_index(n:u8)-> usize{
+const fn table_index(n: u8) -> Option<usize> {
     match n {
-        2 => 0,
-        3 => 1,
-        9 => 2,
-        11 => 3,
-        13 => 4,
-        14 => 5,
-        _ => {panic!("Invalid table index: {}", n)}
+        2 => Some(0),
+        3 => Some(1),
+        9 => Some(2),
+        11 => Some(3),
+        13 => Some(4),
+        14 => Some(5),
+        _ => None,
     }
 }
-fn gmul(n: u8, m: u8) -> u8 {
-    match n {
-        1 => m,
-        _ => {
-            let index = table_index(n);
-            match TABLE.get(index) {
-                Some(row) => {
-                    if let Some(&value) = row.get(m as usize) {
-                        value
-                    } else {
-                        panic!("Index m={} out of bounds in TABLE for n={}", m, n);
-                    }
-                },
-                None => panic!("Value n={} not found in TABLE", n),
-            }
-        }
+
+/// Resolves each coefficient of a MixColumns matrix to its row in `TABLE`, or `None` for
+/// a coefficient of 1. Evaluated at compile time, so a coefficient without a table fails
+/// the build instead of needing a fallback in the cipher core.
+const fn table_rows(matrix: &[[u8; 4]; 4]) -> [[Option<usize>; 4]; 4] {
+    let mut rows = [[None; 4]; 4];
+    let mut i = 0;
+    while i < 16 {
+        let n = matrix[i / 4][i % 4];
+        rows[i / 4][i % 4] = match (n, table_index(n)) {
+            (1, _) => None,
+            (_, Some(index)) => Some(index),
+            (_, None) => panic!("MixColumns coefficient has no multiplication table"),
+        };
+        i += 1;
     }
+    rows
 }
 
-#[derive(Debug)]
+static MIX_ROWS: [[Option<usize>; 4]; 4] = table_rows(&MIX_MATRIX);
+static INV_MIX_ROWS: [[Option<usize>; 4]; 4] = table_rows(&INV_MIX_MATRIX);
+
+/// Flattens a state block into the 16 bytes it was built from.
+///
+/// The state is column-major: byte `i` lives at `block[i / 4][i % 4]`, so each inner
+/// array is one column of the AES state.
+pub fn block_to_bytes(block: &[[u8; 4]; 4]) -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = block[i / 4][i % 4];
+    }
+    bytes
+}
+
+/// Arranges 16 bytes into a column-major state block; the inverse of [`block_to_bytes`].
+pub fn bytes_to_block(bytes: &[u8; 16]) -> [[u8; 4]; 4] {
+    let mut block = [[0u8; 4]; 4];
+    for (i, &byte) in bytes.iter().enumerate() {
+        block[i / 4][i % 4] = byte;
+    }
+    block
+}
+
+/// Overwrites `buf` with zeros in a way the compiler will not optimize away.
+pub(crate) fn wipe(buf: &mut [u8]) {
+    for byte in buf.iter_mut() {
+        // SAFETY: `byte` is a valid, aligned, exclusive reference.
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Zeroes a buffer holding sensitive data, such as decrypted plaintext, once it is no longer needed.
+///
+/// The writes are volatile and fenced, so the compiler cannot drop them as dead stores.
+/// Only `buf` itself is cleared: copies left behind by reallocation (e.g. a `Vec` that
+/// grew), moves, or the compiler spilling values elsewhere cannot be reached. For a
+/// `String`, wipe its bytes through `String::into_bytes`.
+///
+/// # Arguments
+///
+/// * `buf` - The buffer to overwrite with zeros.
+#[cfg(feature = "zeroize")]
+pub fn secure_wipe(buf: &mut [u8]) {
+    wipe(buf);
+}
+
+/// Reports whether blocks are encrypted with the AES-NI hardware instructions.
+///
+/// Every block currently goes through the portable table-based implementation, so this
+/// always returns `false`. It exists so applications can log the backend in use and warn
+/// about the software path; it will return `true` only once a hardware backend is both
+/// compiled in and supported by the running CPU.
+pub fn aes_ni_enabled() -> bool {
+    false
+}
+
+/// Fills an array from the operating system's random number generator.
+///
+/// Panics if the generator is unavailable, which on supported platforms means the
+/// process cannot safely produce keys or nonces at all.
+pub(crate) fn random_array<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    getrandom::getrandom(&mut bytes).expect("the OS random number generator is available");
+    bytes
+}
+
+/// Multiplies `m` by the coefficient whose `TABLE` row is `row`, `None` meaning 1.
+fn gmul(row: Option<usize>, m: u8) -> u8 {
+    match row {
+        Some(index) => TABLE[index][m as usize],
+        None => m,
+    }
+}
+
+/// An AES-128, AES-192 or AES-256 cipher with its expanded key schedule and output settings.
+///
+/// The key size is a runtime property, picked by [`AESteve::new`] from the key length, so
+/// one `AESteve` type covers all three variants: a key size read from a config file needs
+/// no generics or enum at the call site, and every method dispatches on the round count.
+///
+/// Cloning is cheap: clones share one immutable key schedule through an `Arc` instead of
+/// re-running key expansion, and `AESteve` is `Send + Sync`, so a clone per thread (or a
+/// shared reference) is safe. The schedule is zeroed once the last clone is dropped.
+#[derive(Debug, Clone)]
 pub struct AESteve {
-    keys: [[[u8; 4]; 4]; 11],
+    schedule: Arc<KeySchedule>,
+    encoding: Encoding,
+    checksum: bool,
+    max_ciphertext_len: Option<usize>,
+    chunk_size: Option<usize>,
+    sbox: Option<Arc<SBox>>,
+    envelope: bool,
 }
 
 
 impl AESteve {
-    /// Creates a new AES instance with the given 128-bit key.
+    /// Creates a new AES instance with the given key, selecting AES-128, AES-192 or AES-256
+    /// by its length.
     ///
     /// # Arguments
     ///
-    /// * `key` - The 128-bit key (16 bytes).
+    /// * `key` - The 128-bit (16-byte), 192-bit (24-byte) or 256-bit (32-byte) key.
     ///
     /// # Errors
     ///
-    /// Returns `AESError::InvalidKeyLength` if the key length is not 16 bytes.
+    /// Returns `AESError::InvalidKeyLength` if the key length is not 16, 24 or 32 bytes, or
+    /// `AESError::InternalError` if key expansion hits an internal invariant violation
+    /// under `strict-errors`.
     pub fn new(key: &[u8]) -> Result<Self, AESError> {
-        if key.len() != 16 {
+        if !matches!(key.len(), 16 | 24 | 32) {
             return Err(AESError::InvalidKeyLength);
         }
-        let mut key_array = [0u8; 16];
-        key_array.copy_from_slice(key);
-        let keys = Self::expand_key(&key_array);
-        Ok(AESteve { keys })
+        Ok(AESteve {
+            schedule: Arc::new(KeySchedule::expand(key, key.len() / 4)?),
+            encoding: Encoding::default(),
+            checksum: false,
+            max_ciphertext_len: None,
+            chunk_size: None,
+            sbox: None,
+            envelope: false,
+        })
     }
 
-    fn expand_key(key: &[u8; 16]) -> [[[u8; 4]; 4]; 11] {
-        // Key expansion implementation
-        let mut keys: [[[u8; 4]; 4]; 11] = [[[0; 4]; 4]; 11];
-        for col_idx in 0..4 {
-            keys[0][col_idx].copy_from_slice(&key[col_idx * 4..(col_idx + 1) * 4]);
-        }
-
-        for round_idx in 0..10 {
-            for col_idx in 0..4 {
-                if col_idx == 0 {
-                    let last_col = keys[round_idx][3];
-                    let rotated_col = [last_col[1], last_col[2], last_col[3], last_col[0]];
-                    let t1 = keys[round_idx][0][0] ^ (lookup(rotated_col[0]) ^ round_constant(round_idx as u8));
-                    let t2 = keys[round_idx][0][1] ^ lookup(rotated_col[1]);
-                    let t3 = keys[round_idx][0][2] ^ lookup(rotated_col[2]);
-                    let t4 = keys[round_idx][0][3] ^ lookup(rotated_col[3]);
-                    keys[round_idx + 1][0] = [t1, t2, t3, t4];
-                } else {
-                    let t1 = keys[round_idx + 1][col_idx - 1][0] ^ keys[round_idx][col_idx][0];
-                    let t2 = keys[round_idx + 1][col_idx - 1][1] ^ keys[round_idx][col_idx][1];
-                    let t3 = keys[round_idx + 1][col_idx - 1][2] ^ keys[round_idx][col_idx][2];
-                    let t4 = keys[round_idx + 1][col_idx - 1][3] ^ keys[round_idx][col_idx][3];
-                    keys[round_idx + 1][col_idx] = [t1, t2, t3, t4];
-                }
-            }
-        }
-        keys
+    /// Returns the length in bytes of the shortest ciphertext `encrypt_bytes` can produce.
+    ///
+    /// Padding always adds at least one byte, so every padded ciphertext is at least one block.
+    pub fn min_ciphertext_len() -> usize {
+        16
     }
 
-    fn pad(mut message: Vec<u8>) -> Vec<u8> {
-        message.push(0x80);
-        while message.len() % 16 != 0 {
-            message.push(0x00);
+    /// Returns which AES variant this instance runs, as determined by its key length.
+    pub fn variant(&self) -> AesVariant {
+        match self.schedule.rounds {
+            10 => AesVariant::Aes128,
+            12 => AesVariant::Aes192,
+            _ => AesVariant::Aes256,
         }
-        message
     }
 
-    fn depad(message: Vec<u8>) -> Vec<u8> {
-        if let Some(pos) = message.iter().position(|&n| n == 0x80) {
-            message[0..pos].to_vec()
-        } else {
-            message
+    /// Returns the number of rounds this instance runs: 10, 12 or 14 for AES-128, -192 or -256.
+    pub fn rounds(&self) -> usize {
+        self.schedule.rounds
+    }
+
+    /// Sets the text encoding used for ciphertext by `encrypt_ecb` and `decrypt_ecb`.
+    ///
+    /// Defaults to `Encoding::Base64`.
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Caps the decoded ciphertext length accepted by `decrypt_ecb`, `decrypt_auto`,
+    /// `decrypt_cbc_base64` and `decrypt_cbc_parts`.
+    ///
+    /// The decoded length is computed from the encoded string's length before anything is
+    /// decoded, so oversized untrusted input is rejected without allocating for it.
+    /// Defaults to no limit.
+    pub fn with_max_ciphertext_len(mut self, max_len: usize) -> Self {
+        self.max_ciphertext_len = Some(max_len);
+        self
+    }
+
+    /// Sets how many blocks each rayon task encrypts in the parallel ECB paths.
+    ///
+    /// Larger chunks mean fewer tasks to schedule and better locality on NUMA machines;
+    /// smaller chunks balance load more finely. A multiple of 4 keeps every task on the
+    /// four-block path. `0` is treated as `1`, and sizes whose byte length would overflow
+    /// `usize` are capped, leaving the whole buffer to a single task. Defaults to splitting
+    /// the buffer into a few chunks per rayon thread.
+    pub fn with_chunk_size(mut self, blocks: usize) -> Self {
+        self.chunk_size = Some(blocks.clamp(1, usize::MAX / 16));
+        self
+    }
+
+    /// Returns the number of blocks per rayon task for a buffer of `block_count` blocks.
+    fn blocks_per_task(&self, block_count: usize) -> usize {
+        self.chunk_size.unwrap_or_else(|| {
+            block_count
+                .div_ceil(rayon::current_num_threads() * 4)
+                .next_multiple_of(4)
+                .max(4)
+        })
+    }
+
+    fn check_ciphertext_len(&self, len: usize) -> Result<(), AESError> {
+        match self.max_ciphertext_len {
+            Some(max_len) if len > max_len => Err(AESError::CiphertextTooLarge),
+            _ => Ok(()),
         }
     }
 
+    fn decode_ciphertext(&self, encrypted_message: &str) -> Result<Vec<u8>, AESError> {
+        self.check_ciphertext_len(Encoding::decoded_len(encrypted_message))?;
+        Ok(self.encoding.decode(encrypted_message)?)
+    }
+
     fn make_blocks(padded_message: Vec<u8>) -> Vec<[[u8; 4]; 4]> {
         let mut blocks = Vec::new();
         for chunk in padded_message.chunks(16) {
-            let mut block = [[0u8; 4]; 4];
-            for (i, &byte) in chunk.iter().enumerate() {
-                block[i / 4][i % 4] = byte;
-            }
-            blocks.push(block);
+            let mut bytes = [0u8; 16];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            blocks.push(bytes_to_block(&bytes));
         }
         blocks
     }
@@ -329,21 +637,29 @@ impl AESteve {
         new_block
     }
 
-    fn sub_bytes(block: [[u8; 4]; 4]) -> [[u8; 4]; 4] {
+    fn sub_bytes(&self, block: [[u8; 4]; 4]) -> [[u8; 4]; 4] {
         let mut new_block = [[0u8; 4]; 4];
         for col_idx in 0..4 {
             for row_idx in 0..4 {
-                new_block[col_idx][row_idx] = lookup(block[col_idx][row_idx]);
+                let byte = block[col_idx][row_idx];
+                new_block[col_idx][row_idx] = match &self.sbox {
+                    Some(sbox) => sbox.forward[byte as usize],
+                    None => lookup(byte),
+                };
             }
         }
         new_block
     }
 
-    fn inv_sub_bytes(block: [[u8; 4]; 4]) -> [[u8; 4]; 4] {
+    fn inv_sub_bytes(&self, block: [[u8; 4]; 4]) -> [[u8; 4]; 4] {
         let mut new_block = [[0u8; 4]; 4];
         for col_idx in 0..4 {
             for row_idx in 0..4 {
-                new_block[col_idx][row_idx] = reverse_lookup(block[col_idx][row_idx]);
+                let byte = block[col_idx][row_idx];
+                new_block[col_idx][row_idx] = match &self.sbox {
+                    Some(sbox) => sbox.inverse[byte as usize],
+                    None => reverse_lookup(byte),
+                };
             }
         }
         new_block
@@ -387,7 +703,7 @@ impl AESteve {
         for i in 0..4 {
             for j in 0..4 {
                 for k in 0..4 {
-                    new_block[j][i] ^= gmul(MIX_MATRIX[i][k], block_t[k][j]);
+                    new_block[j][i] ^= gmul(MIX_ROWS[i][k], block_t[k][j]);
                 }
             }
         }
@@ -400,7 +716,7 @@ impl AESteve {
         for i in 0..4 {
             for j in 0..4 {
                 for k in 0..4 {
-                    new_block[j][i] ^= gmul(INV_MIX_MATRIX[i][k], block_t[k][j]);
+                    new_block[j][i] ^= gmul(INV_MIX_ROWS[i][k], block_t[k][j]);
                 }
             }
         }
@@ -408,70 +724,369 @@ impl AESteve {
     }
 
     fn encrypt_block(&self, block: [[u8; 4]; 4]) -> [[u8; 4]; 4] {
-        let mut new_block = Self::add_round_key(self.keys[0], block);
-        for i in 0..9 {
-            new_block = Self::sub_bytes(new_block);
+        let mut new_block = Self::add_round_key(self.schedule[0], block);
+        for round in 1..self.schedule.rounds {
+            new_block = self.sub_bytes(new_block);
             new_block = Self::shift_rows(new_block);
             new_block = Self::mix_columns(new_block);
-            new_block = Self::add_round_key(self.keys[i + 1], new_block);
+            new_block = Self::add_round_key(self.schedule[round], new_block);
         }
-        new_block = Self::sub_bytes(new_block);
-        new_block = Self::shift_rows(new_block);
-        new_block = Self::add_round_key(self.keys[10], new_block);
+        self.final_round(new_block)
+    }
 
-        new_block
+    /// The last encryption round, which omits MixColumns.
+    fn final_round(&self, block: [[u8; 4]; 4]) -> [[u8; 4]; 4] {
+        let new_block = self.sub_bytes(block);
+        let new_block = Self::shift_rows(new_block);
+        Self::add_round_key(self.schedule[self.schedule.rounds], new_block)
+    }
+
+    pub(crate) fn encrypt_block_bytes(&self, block: &[u8; 16]) -> [u8; 16] {
+        block_to_bytes(&self.encrypt_block(bytes_to_block(block)))
+    }
+
+    pub(crate) fn decrypt_block_bytes(&self, block: &[u8; 16]) -> [u8; 16] {
+        block_to_bytes(&self.decrypt_block(bytes_to_block(block)))
+    }
+
+    /// Encrypts exactly one 16-byte block, without allocating.
+    ///
+    /// This is raw single-block ECB: no padding, no encoding and no authentication. Equal
+    /// inputs give equal outputs, so it only suits fixed-size fields that never repeat.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The 16 plaintext bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `[u8; 16]` - The 16 ciphertext bytes.
+    pub fn encrypt_array(&self, block: [u8; 16]) -> [u8; 16] {
+        self.encrypt_block_bytes(&block)
+    }
+
+    /// Decrypts one 16-byte block produced by [`AESteve::encrypt_array`].
+    pub fn decrypt_array(&self, block: [u8; 16]) -> [u8; 16] {
+        self.decrypt_block_bytes(&block)
+    }
+
+    /// Decrypts one 16-byte block straight into `out`, without allocating.
+    ///
+    /// The single-block building block for modes implemented on top of this crate in
+    /// allocation-free code. Like [`AESteve::decrypt_array`], no padding is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The 16 ciphertext bytes.
+    /// * `out` - Receives the 16 plaintext bytes.
+    pub fn decrypt_block_into(&self, input: &[u8; 16], out: &mut [u8; 16]) {
+        *out = self.decrypt_block_bytes(input);
+    }
+
+    /// Encrypts fixed-size 16-byte records in parallel into one contiguous buffer.
+    ///
+    /// Each record is one raw ECB block, as [`AESteve::encrypt_array`]: no padding, so
+    /// equal records give equal ciphertext. Record `i` ends up at `i * 16` in the output.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - The records to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The encrypted records, `records.len() * 16` bytes in order.
+    pub fn encrypt_records(&self, records: &[[u8; 16]]) -> Vec<u8> {
+        self.encrypt_blocks(records.as_flattened().to_vec())
     }
 
     fn decrypt_block(&self, block: [[u8; 4]; 4]) -> [[u8; 4]; 4] {
-        let mut new_block = Self::add_round_key(self.keys[10], block);
+        let mut new_block = Self::add_round_key(self.schedule[self.schedule.rounds], block);
         new_block = Self::inv_shift_rows(new_block);
-        new_block = Self::inv_sub_bytes(new_block);
-        for i in 0..9 {
-            new_block = Self::add_round_key(self.keys[9 - i], new_block);
+        new_block = self.inv_sub_bytes(new_block);
+        for round in (1..self.schedule.rounds).rev() {
+            new_block = Self::add_round_key(self.schedule[round], new_block);
             new_block = Self::inv_mix_columns(new_block);
             new_block = Self::inv_shift_rows(new_block);
-            new_block = Self::inv_sub_bytes(new_block);
+            new_block = self.inv_sub_bytes(new_block);
         }
-        new_block = Self::add_round_key(self.keys[0], new_block);
+        new_block = Self::add_round_key(self.schedule[0], new_block);
 
         new_block
     }
 
-    /// Encrypts the given message.
+    /// Returns the expanded key for `round` in standard AES byte order.
+    ///
+    /// Round 0 is the first 16 bytes of the cipher key and the last round is 10, 12 or 14
+    /// for AES-128, -192 or -256. The bytes are in the
+    /// order FIPS-197 prints them, so they can be compared directly against published
+    /// schedules or another implementation.
     ///
     /// # Arguments
     ///
-    /// * `message` - The message to be encrypted.
+    /// * `round` - The round whose key to return, from 0 to the number of rounds.
     ///
     /// # Returns
     ///
-    /// * `String` - The encrypted message in Base64 format.
+    /// * `Option<[u8; 16]>` - The round key, or `None` if `round` is out of range.
+    pub fn round_key_bytes(&self, round: usize) -> Option<[u8; 16]> {
+        self.schedule.get(round).map(block_to_bytes)
+    }
+
+    /// Encrypts the given bytes, filling the final block with `padding`.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The bytes to be encrypted.
+    /// * `padding` - The padding scheme used to fill the final block.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext.
+    pub fn encrypt_with_padding<P: PaddingScheme + ?Sized>(&self, message: &[u8], padding: &P) -> Vec<u8> {
+        let mut padded_message = message.to_vec();
+        padding.pad(&mut padded_message);
+        self.encrypt_blocks(padded_message)
+    }
+
+    fn encrypt_blocks(&self, mut padded_message: Vec<u8>) -> Vec<u8> {
+        let task_len = self.blocks_per_task(padded_message.len() / 16) * 16;
+        padded_message
+            .par_chunks_mut(task_len)
+            .for_each(|task| self.encrypt_run(task));
+        padded_message
+    }
+
+    /// Encrypts a run of whole blocks in place, four at a time where possible.
+    ///
+    /// The four-block path is specialized for the standard S-box, so a custom S-box
+    /// encrypts every block individually.
+    fn encrypt_run(&self, run: &mut [u8]) {
+        let grouped_len = if self.sbox.is_none() { run.len() / 64 * 64 } else { 0 };
+        let (groups, rest) = run.split_at_mut(grouped_len);
+        for group in groups.chunks_exact_mut(64) {
+            let mut blocks = [[0u8; 16]; 4];
+            for (block, chunk) in blocks.iter_mut().zip(group.chunks_exact(16)) {
+                block.copy_from_slice(chunk);
+            }
+            self.encrypt_4_blocks(&mut blocks);
+            group.copy_from_slice(blocks.as_flattened());
+        }
+        for chunk in rest.chunks_exact_mut(16) {
+            let block: &mut [u8; 16] = chunk.try_into().unwrap();
+            *block = self.encrypt_block_bytes(block);
+        }
+    }
+
+    /// Decrypts the given raw ciphertext and strips `padding` from the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The raw ciphertext.
+    /// * `padding` - The padding scheme the ciphertext was produced with.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted bytes.
     ///
     /// # Errors
     ///
-    /// Returns `AESError` if an error occurs during encryption.
-    pub fn encrypt(&self, message: String) -> Result<String, AESError> {
-        let message = message.as_bytes().to_vec();
-        let padded_message = Self::pad(message);
-        let blocks = Self::make_blocks(padded_message);
+    /// Returns `AESError::CiphertextTooShort` if `ciphertext` is shorter than one block,
+    /// `AESError::InvalidCiphertextLength` if it is not a whole number of blocks, or
+    /// `AESError::InvalidPadding` if the decrypted bytes are not padded with `padding`.
+    pub fn decrypt_with_padding<P: PaddingScheme + ?Sized>(&self, ciphertext: &[u8], padding: &P) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < Self::min_ciphertext_len() {
+            return Err(AESError::CiphertextTooShort);
+        }
+        if !ciphertext.len().is_multiple_of(16) {
+            return Err(AESError::InvalidCiphertextLength);
+        }
+        padding.depad(self.decrypt_blocks(ciphertext))
+    }
 
-        let encrypted_blocks: Vec<[[u8; 4]; 4]> = blocks
+    /// Decrypts the given raw ciphertext and truncates it to a known plaintext length.
+    ///
+    /// No padding is inspected, so plaintext ending in bytes that look like padding
+    /// (e.g. `0x80` or `0x00`) is returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The raw ciphertext.
+    /// * `plaintext_len` - The length of the original plaintext.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The first `plaintext_len` decrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidCiphertextLength` if `ciphertext` is not a positive whole
+    /// number of blocks, or `AESError::InvalidPlaintextLength` if `plaintext_len` exceeds
+    /// the decrypted length.
+    pub fn decrypt_exact(&self, ciphertext: &[u8], plaintext_len: usize) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < Self::min_ciphertext_len() || !ciphertext.len().is_multiple_of(16) {
+            return Err(AESError::InvalidCiphertextLength);
+        }
+        let mut decrypted = self.decrypt_blocks(ciphertext);
+        if plaintext_len > decrypted.len() {
+            return Err(AESError::InvalidPlaintextLength);
+        }
+        decrypted.truncate(plaintext_len);
+        Ok(decrypted)
+    }
+
+    /// Lazily decrypts 16-byte ciphertext blocks in ECB mode as they are pulled.
+    ///
+    /// Each block is decrypted independently and no padding is removed; the caller is
+    /// responsible for stripping padding from the final block (e.g. with [`PaddingScheme::depad`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `blocks` - The ciphertext blocks, in order.
+    ///
+    /// # Returns
+    ///
+    /// * `impl Iterator<Item = [u8; 16]>` - The plaintext blocks, in the same order.
+    pub fn decrypt_blocks_iter<'a, I>(&'a self, blocks: I) -> impl Iterator<Item = [u8; 16]> + 'a
+    where
+        I: IntoIterator<Item = [u8; 16]>,
+        I::IntoIter: 'a,
+    {
+        blocks.into_iter().map(move |block| self.decrypt_block_bytes(&block))
+    }
+
+    fn decrypt_blocks(&self, ciphertext: &[u8]) -> Vec<u8> {
+        let blocks = Self::make_blocks(ciphertext.to_vec());
+
+        let decrypted_blocks: Vec<[[u8; 4]; 4]> = blocks
             .into_par_iter()
-            .map(|block| self.encrypt_block(block))
+            .map(|block| self.decrypt_block(block))
             .collect();
 
-        let flattened: Vec<u8> = encrypted_blocks
+        decrypted_blocks
             .into_iter()
-            .flat_map(|array4x4| array4x4.into_iter().flat_map(|array4| array4.into_iter()))
-            .collect();
-        Ok(STANDARD.encode(&flattened))
+            .flat_map(|block| block_to_bytes(&block))
+            .collect()
+    }
+
+    /// Encrypts the given bytes in ECB mode with the default ISO/IEC 7816-4 padding.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext.
+    pub fn encrypt_bytes(&self, data: &[u8]) -> Vec<u8> {
+        self.encrypt_bytes_with_capacity(data, data.len())
     }
 
-    /// Decrypts the given encrypted message.
+    /// Runs the [`AESteve::encrypt_bytes`] pipeline with a working buffer that starts with
+    /// room for `capacity` bytes, so callers that know the padded length avoid regrowing it.
+    pub(crate) fn encrypt_bytes_with_capacity(&self, data: &[u8], capacity: usize) -> Vec<u8> {
+        let mut message = Vec::with_capacity(capacity);
+        message.extend_from_slice(data);
+        self.append_checksum(&mut message);
+        Padding::Iso7816.pad(&mut message);
+        self.seal_envelope(self.encrypt_blocks(message))
+    }
+
+    /// Encrypts the given bytes like [`AESteve::encrypt_bytes`], calling `on_block` after each block.
+    ///
+    /// Blocks are processed in order on the calling thread rather than in parallel, so
+    /// `on_block` needs no synchronization. Useful for driving progress reporting.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted.
+    /// * `on_block` - Called with the index of each block once it has been encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext.
+    pub fn encrypt_bytes_with_hook(&self, data: &[u8], mut on_block: impl FnMut(usize)) -> Vec<u8> {
+        let mut padded_message = data.to_vec();
+        self.append_checksum(&mut padded_message);
+        Padding::Iso7816.pad(&mut padded_message);
+
+        let mut encrypted = Vec::with_capacity(padded_message.len());
+        for (i, block) in Self::make_blocks(padded_message).into_iter().enumerate() {
+            encrypted.extend_from_slice(&block_to_bytes(&self.encrypt_block(block)));
+            on_block(i);
+        }
+        self.seal_envelope(encrypted)
+    }
+
+    /// Decrypts raw ciphertext produced by [`AESteve::encrypt_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The raw ciphertext.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::CiphertextTooShort` if `ciphertext` is shorter than one block,
+    /// `AESError::InvalidCiphertextLength` if it is not a whole number of blocks,
+    /// `AESError::InvalidPadding` if the decrypted bytes are not validly padded, or
+    /// `AESError::ChecksumMismatch` if checksums are enabled and the plaintext is corrupted.
+    pub fn decrypt_bytes(&self, ciphertext: &[u8]) -> Result<Vec<u8>, AESError> {
+        let mut message = self.decrypt_with_padding(self.open_envelope(ciphertext)?, &Padding::Iso7816)?;
+        let payload_len = self.verify_checksum(&message)?;
+        message.truncate(payload_len);
+        Ok(message)
+    }
+
+    /// Encrypts the given message in ECB mode.
+    ///
+    /// ECB encrypts identical 16-byte plaintext blocks to identical ciphertext blocks, so it
+    /// leaks the structure of the message. Only use it for data without repeated blocks.
     ///
     /// # Arguments
     ///
-    /// * `encrypted_message` - The encrypted message in Base64 format.
+    /// * `message` - The message to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The encrypted message in the configured encoding (Base64 by default).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError` if an error occurs during encryption.
+    pub fn encrypt_ecb(&self, message: String) -> Result<String, AESError> {
+        let encrypted_message = self.encrypt_bytes(message.as_bytes());
+        Ok(self.encoding.encode(&encrypted_message))
+    }
+
+    /// Encrypts the given message like [`AESteve::encrypt_ecb`], returning both the raw
+    /// ciphertext and its encoded form from a single encryption.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `(Vec<u8>, String)` - The raw ciphertext, and the same ciphertext in the configured
+    ///   encoding (Base64 by default).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError` if an error occurs during encryption.
+    pub fn encrypt_both(&self, message: String) -> Result<(Vec<u8>, String), AESError> {
+        let encrypted_message = self.encrypt_bytes(message.as_bytes());
+        let encoded = self.encoding.encode(&encrypted_message);
+        Ok((encrypted_message, encoded))
+    }
+
+    /// Decrypts the given ECB-encrypted message.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted_message` - The encrypted message in the configured encoding.
     ///
     /// # Returns
     ///
@@ -480,23 +1095,141 @@ impl AESteve {
     /// # Errors
     ///
     /// Returns `AESError` if an error occurs during decryption.
-    pub fn decrypt(&self, encrypted_message: String) -> Result<String, AESError> {
-        let decoded_message = STANDARD.decode(encrypted_message).map_err(AESError::InvalidBase64)?;
-        let blocks = Self::make_blocks(decoded_message);
+    pub fn decrypt_ecb(&self, encrypted_message: String) -> Result<String, AESError> {
+        let decoded_message = self.decode_ciphertext(&encrypted_message)?;
+        let depadded_message = self.decrypt_bytes(&decoded_message)?;
+        String::from_utf8(depadded_message).map_err(AESError::InvalidUTF8)
+    }
 
-        let decrypted_blocks: Vec<[[u8; 4]; 4]> = blocks
+    /// Decrypts newline-separated ECB-encrypted messages, such as a log of encoded ciphertexts.
+    ///
+    /// Lines are split like [`str::lines`] (so `\r\n` endings and a trailing newline are
+    /// handled) and decrypted in parallel. Each line gets its own result, so one corrupt
+    /// line does not abort the batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The encrypted messages in the configured encoding, one per line.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Result<String, AESError>>` - The result of decrypting each line, in line order.
+    pub fn decrypt_lines(&self, input: &str) -> Vec<Result<String, AESError>> {
+        let lines: Vec<&str> = input.lines().collect();
+        lines
             .into_par_iter()
-            .map(|block| self.decrypt_block(block))
-            .collect();
-
-        let flattened: Vec<u8> = decrypted_blocks
-            .into_iter()
-            .flat_map(|array4x4| array4x4.into_iter().flat_map(|array4| array4.into_iter()))
-            .collect();
+            .map(|line| self.decrypt_ecb(line.to_string()))
+            .collect()
+    }
 
-        let depadded_message = Self::depad(flattened);
+    /// Decrypts an ECB-encrypted message that may be either encoded text or raw bytes.
+    ///
+    /// `input` is treated as encoded text if it is valid UTF-8, decodes cleanly with the
+    /// configured encoding, and decodes to a nonzero multiple of 16 bytes; otherwise it is
+    /// decrypted as raw ciphertext. Raw ciphertext made up only of encoding characters would
+    /// be misclassified, but for random ciphertext this is vanishingly unlikely
+    /// (below 2^-32 even for a single block). Meant for migrations, not as a general format.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The encoded or raw ciphertext.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The decrypted message.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError` if an error occurs during decryption.
+    pub fn decrypt_auto(&self, input: &[u8]) -> Result<String, AESError> {
+        let decoded = match std::str::from_utf8(input).map(|text| self.decode_ciphertext(text)) {
+            Ok(Err(AESError::CiphertextTooLarge)) => return Err(AESError::CiphertextTooLarge),
+            Ok(Ok(bytes)) if self.open_envelope(&bytes).is_ok_and(|payload| {
+                !payload.is_empty() && payload.len().is_multiple_of(16)
+            }) => Some(bytes),
+            _ => None,
+        };
+        let depadded_message = match decoded {
+            Some(ciphertext) => self.decrypt_bytes(&ciphertext)?,
+            None => {
+                self.check_ciphertext_len(input.len())?;
+                self.decrypt_bytes(input)?
+            }
+        };
         String::from_utf8(depadded_message).map_err(AESError::InvalidUTF8)
     }
+
+    /// Encrypts the given message in ECB mode. Alias of [`AESteve::encrypt_ecb`].
+    #[deprecated(note = "ECB is insecure for structured data; use encrypt_cbc/encrypt_gcm")]
+    pub fn encrypt(&self, message: String) -> Result<String, AESError> {
+        self.encrypt_ecb(message)
+    }
+
+    /// Decrypts the given ECB-encrypted message. Alias of [`AESteve::decrypt_ecb`].
+    #[deprecated(note = "ECB is insecure for structured data; use decrypt_cbc/decrypt_gcm")]
+    pub fn decrypt(&self, encrypted_message: String) -> Result<String, AESError> {
+        self.decrypt_ecb(encrypted_message)
+    }
+
+    /// Cheaply checks that `encrypted_message` is shaped like ciphertext, without decrypting.
+    ///
+    /// Passes if the text decodes in the configured encoding within the configured size cap,
+    /// carries a valid envelope header if envelopes are enabled, and holds a nonzero
+    /// multiple of 16 bytes. Meant for rejecting junk before scheduling decryption work;
+    /// passing does not mean decryption will succeed.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted_message` - The encrypted message in the configured encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if `encrypted_message` has the shape of ciphertext.
+    pub fn is_valid_ciphertext_shape(&self, encrypted_message: &str) -> bool {
+        self.decode_ciphertext(encrypted_message).is_ok_and(|decoded| {
+            self.open_envelope(&decoded)
+                .is_ok_and(|payload| !payload.is_empty() && payload.len().is_multiple_of(16))
+        })
+    }
+
+    /// Checks whether the given encrypted message decrypts to validly padded UTF-8.
+    ///
+    /// The plaintext is never returned; the decrypted buffer is zeroed before this returns.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted_message` - The encrypted message in the configured encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if `decrypt_ecb` would succeed on `encrypted_message`.
+    pub fn verify_decryptable(&self, encrypted_message: &str) -> bool {
+        let Ok(decoded_message) = self.decode_ciphertext(encrypted_message) else {
+            return false;
+        };
+        let Ok(payload) = self.open_envelope(&decoded_message) else {
+            return false;
+        };
+        if payload.len() < Self::min_ciphertext_len() || !payload.len().is_multiple_of(16) {
+            return false;
+        }
+        let mut decrypted = self.decrypt_blocks(payload);
+        let last_block = decrypted.len().saturating_sub(16);
+
+        let valid = match Padding::Iso7816.depad(decrypted[last_block..].to_vec()) {
+            Ok(mut tail) => {
+                let plaintext_len = last_block + tail.len();
+                wipe(&mut tail);
+                match self.verify_checksum(&decrypted[..plaintext_len]) {
+                    Ok(payload_len) => std::str::from_utf8(&decrypted[..payload_len]).is_ok(),
+                    Err(_) => false,
+                }
+            }
+            Err(_) => false,
+        };
+        wipe(&mut decrypted);
+        valid
+    }
 }
 
 #[cfg(test)]
@@ -507,10 +1240,11 @@ mod tests {
     fn test_expand_key() {
         let key = [0u8; 16];
         let aesteve = AESteve::new(&key).unwrap();
-        assert_eq!(aesteve.keys[0][0], [0, 0, 0, 0]);
+        assert_eq!(aesteve.schedule[0][0], [0, 0, 0, 0]);
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_encrypt_decrypt() {
         let key = [0u8; 16];
         let aesteve = AESteve::new(&key).unwrap();
@@ -522,11 +1256,477 @@ mod tests {
         assert_eq!(decrypted_message, message);
     }
 
+    #[test]
+    fn test_encrypt_decrypt_ecb() {
+        let key = [0u8; 16];
+        let aesteve = AESteve::new(&key).unwrap();
+        let message = String::from("This is a test!");
+
+        let encrypted_message = aesteve.encrypt_ecb(message.clone()).unwrap();
+        #[allow(deprecated)]
+        let alias_encrypted = aesteve.encrypt(message.clone()).unwrap();
+        assert_eq!(encrypted_message, alias_encrypted);
+        assert_eq!(aesteve.decrypt_ecb(encrypted_message).unwrap(), message);
+    }
+
+    #[test]
+    fn test_decrypt_exact() {
+        let key = [0u8; 16];
+        let aesteve = AESteve::new(&key).unwrap();
+        let message = [0x01, 0x80, 0x00, 0x80, 0xFF, 0x80, 0x00, 0x00];
+
+        let encrypted = aesteve.encrypt_with_padding(&message, &Padding::Iso7816);
+        assert_eq!(aesteve.decrypt_exact(&encrypted, message.len()).unwrap(), message);
+        assert!(matches!(aesteve.decrypt_exact(&encrypted, 17), Err(AESError::InvalidPlaintextLength)));
+    }
+
+    #[test]
+    fn test_decrypt_exact_misaligned() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let encrypted = aesteve.encrypt_with_padding(&[0x42u8; 20], &Padding::Iso7816);
+        assert!(matches!(aesteve.decrypt_exact(&encrypted[..20], 20), Err(AESError::InvalidCiphertextLength)));
+        assert!(matches!(aesteve.decrypt_exact(&encrypted[..8], 8), Err(AESError::InvalidCiphertextLength)));
+        assert!(matches!(aesteve.decrypt_exact(&[], 0), Err(AESError::InvalidCiphertextLength)));
+    }
+
+    #[test]
+    fn test_encrypt_bytes_with_hook() {
+        let key = [0u8; 16];
+        let aesteve = AESteve::new(&key).unwrap();
+        let data = [0x42u8; 40];
+
+        let mut seen = Vec::new();
+        let encrypted = aesteve.encrypt_bytes_with_hook(&data, |i| seen.push(i));
+        assert_eq!(seen, vec![0, 1, 2]);
+        assert_eq!(encrypted, aesteve.encrypt_bytes(&data));
+        assert_eq!(aesteve.decrypt_bytes(&encrypted).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decrypt_auto() {
+        let key = [0u8; 16];
+        let aesteve = AESteve::new(&key).unwrap();
+        let message = "migrated record";
+
+        let encoded = aesteve.encrypt_ecb(message.to_string()).unwrap();
+        assert_eq!(aesteve.decrypt_auto(encoded.as_bytes()).unwrap(), message);
+
+        let raw = aesteve.encrypt_bytes(message.as_bytes());
+        assert_eq!(aesteve.decrypt_auto(&raw).unwrap(), message);
+    }
+
+    #[test]
+    fn test_ciphertext_too_short() {
+        let key = [0u8; 16];
+        let aesteve = AESteve::new(&key).unwrap();
+        let short = Encoding::Base64.encode(&[1, 2, 3, 4]);
+        assert!(matches!(aesteve.decrypt_ecb(short), Err(AESError::CiphertextTooShort)));
+        assert!(matches!(aesteve.decrypt_bytes(&[]), Err(AESError::CiphertextTooShort)));
+        assert_eq!(aesteve.encrypt_bytes(&[]).len(), AESteve::min_ciphertext_len());
+    }
+
+    #[test]
+    fn test_invalid_ciphertext_length() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let ciphertext = aesteve.encrypt_bytes(b"a message longer than one block");
+        let truncated = Encoding::Base64.encode(&ciphertext[..ciphertext.len() - 3]);
+        assert!(matches!(aesteve.decrypt_ecb(truncated), Err(AESError::InvalidCiphertextLength)));
+        assert!(matches!(aesteve.decrypt_bytes(&ciphertext[..17]), Err(AESError::InvalidCiphertextLength)));
+        assert!(matches!(
+            aesteve.decrypt_with_padding(&ciphertext[..20], &Padding::Pkcs7),
+            Err(AESError::InvalidCiphertextLength)
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_blocks_iter() {
+        let key = [0u8; 16];
+        let aesteve = AESteve::new(&key).unwrap();
+        let ciphertext = aesteve.encrypt_bytes(&[0x33u8; 50]);
+
+        let blocks = ciphertext.chunks_exact(16).map(|chunk| <[u8; 16]>::try_from(chunk).unwrap());
+        let lazy: Vec<u8> = aesteve.decrypt_blocks_iter(blocks).flatten().collect();
+        assert_eq!(lazy, aesteve.decrypt_blocks(&ciphertext));
+    }
+
+    #[test]
+    fn test_ciphertext_too_large() {
+        let key = [0u8; 16];
+        let aesteve = AESteve::new(&key).unwrap().with_max_ciphertext_len(32);
+        let fits = aesteve.encrypt_ecb("x".repeat(31)).unwrap();
+        assert_eq!(aesteve.decrypt_ecb(fits).unwrap(), "x".repeat(31));
+
+        let oversized = "A".repeat(1 << 20);
+        assert!(matches!(aesteve.decrypt_ecb(oversized.clone()), Err(AESError::CiphertextTooLarge)));
+        assert!(matches!(aesteve.decrypt_auto(oversized.as_bytes()), Err(AESError::CiphertextTooLarge)));
+    }
+
+    #[test]
+    fn test_chunk_size_output_unchanged() {
+        let key = [0u8; 16];
+        let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+        let expected = AESteve::new(&key).unwrap().encrypt_bytes(&data);
+        for blocks in [0, 1, 3, 4, 7, 64, 1000, 1 << 60, usize::MAX] {
+            let aesteve = AESteve::new(&key).unwrap().with_chunk_size(blocks);
+            assert_eq!(aesteve.encrypt_bytes(&data), expected);
+            assert_eq!(aesteve.decrypt_bytes(&expected).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_clone_shares_schedule() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let clone = aesteve.clone().with_encoding(Encoding::Base64Url);
+        assert!(Arc::ptr_eq(&aesteve.schedule, &clone.schedule));
+        assert_eq!(clone.encrypt_bytes(b"shared"), aesteve.encrypt_bytes(b"shared"));
+    }
+
+    #[test]
+    fn test_encrypt_array_fips_197() {
+        // FIPS-197, appendix C.1.
+        let key: Vec<u8> = (0u8..16).collect();
+        let aesteve = AESteve::new(&key).unwrap();
+        let plaintext = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+            0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+        ];
+        let ciphertext = [
+            0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30,
+            0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a,
+        ];
+        assert_eq!(aesteve.encrypt_array(plaintext), ciphertext);
+        assert_eq!(aesteve.decrypt_array(ciphertext), plaintext);
+    }
+
+    #[test]
+    fn test_final_round_omits_mix_columns() {
+        // FIPS-197, appendix B: the state at the start of round 10, and the output.
+        let key = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+        ];
+        let plaintext = [
+            0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07, 0x34,
+        ];
+        let before_final = [
+            0xeb, 0x40, 0xf2, 0x1e, 0x59, 0x2e, 0x38, 0x84, 0x8b, 0xa1, 0x13, 0xe7, 0x1b, 0xc3, 0x42, 0xd2,
+        ];
+        let ciphertext = [
+            0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a, 0x0b, 0x32,
+        ];
+        let aesteve = AESteve::new(&key).unwrap();
+        let state = bytes_to_block(&before_final);
+
+        let manual = AESteve::add_round_key(aesteve.schedule[10], AESteve::shift_rows(aesteve.sub_bytes(state)));
+        let with_mix_columns = AESteve::add_round_key(
+            aesteve.schedule[10],
+            AESteve::mix_columns(AESteve::shift_rows(aesteve.sub_bytes(state))),
+        );
+        assert_eq!(block_to_bytes(&manual), ciphertext);
+        assert_ne!(block_to_bytes(&with_mix_columns), ciphertext);
+        assert_eq!(aesteve.final_round(state), manual);
+
+        assert_eq!(aesteve.encrypt_array(plaintext), ciphertext);
+        let mut blocks = [plaintext; 4];
+        aesteve.encrypt_4_blocks(&mut blocks);
+        assert_eq!(blocks, [ciphertext; 4]);
+    }
+
+    #[test]
+    fn test_encrypt_records() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let records: Vec<[u8; 16]> = (0..67u8).map(|i| [i; 16]).collect();
+        let encrypted = aesteve.encrypt_records(&records);
+        assert_eq!(encrypted.len(), records.len() * 16);
+        for (record, chunk) in records.iter().zip(encrypted.chunks(16)) {
+            assert_eq!(chunk, aesteve.encrypt_array(*record));
+        }
+        assert!(aesteve.encrypt_records(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_decrypt_block_into() {
+        // FIPS-197, appendix C.1.
+        let key: Vec<u8> = (0u8..16).collect();
+        let aesteve = AESteve::new(&key).unwrap();
+        let ciphertext = [
+            0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30,
+            0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a,
+        ];
+        let mut out = [0u8; 16];
+        aesteve.decrypt_block_into(&ciphertext, &mut out);
+        assert_eq!(out.to_vec(), aesteve.decrypt_exact(&ciphertext, 16).unwrap());
+        assert_eq!(
+            out,
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff]
+        );
+    }
+
+    #[test]
+    fn test_matches_rustcrypto_aes() {
+        use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+
+        for _ in 0..256 {
+            let key: [u8; 16] = random_array();
+            let plaintext: [u8; 16] = random_array();
+            let aesteve = AESteve::new(&key).unwrap();
+            let reference = aes::Aes128::new(&key.into());
+
+            let mut expected = plaintext.into();
+            reference.encrypt_block(&mut expected);
+            let ciphertext = aesteve.encrypt_array(plaintext);
+            assert_eq!(ciphertext, <[u8; 16]>::from(expected), "key {:02x?}", key);
+
+            let mut decrypted = ciphertext.into();
+            reference.decrypt_block(&mut decrypted);
+            assert_eq!(aesteve.decrypt_array(ciphertext), <[u8; 16]>::from(decrypted));
+        }
+    }
+
+    #[test]
+    fn test_matches_rustcrypto_aes192() {
+        use aes::cipher::{BlockEncrypt, KeyInit};
+
+        for _ in 0..64 {
+            let key: [u8; 24] = random_array();
+            let plaintext: [u8; 16] = random_array();
+            let aesteve = AESteve::new(&key).unwrap();
+
+            let mut expected = plaintext.into();
+            aes::Aes192::new(&key.into()).encrypt_block(&mut expected);
+            let ciphertext = aesteve.encrypt_array(plaintext);
+            assert_eq!(ciphertext, <[u8; 16]>::from(expected), "key {:02x?}", key);
+            assert_eq!(aesteve.decrypt_array(ciphertext), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_matches_rustcrypto_aes256() {
+        use aes::cipher::{BlockEncrypt, KeyInit};
+
+        for _ in 0..64 {
+            let key: [u8; 32] = random_array();
+            let plaintext: [u8; 16] = random_array();
+            let aesteve = AESteve::new(&key).unwrap();
+
+            let mut expected = plaintext.into();
+            aes::Aes256::new(&key.into()).encrypt_block(&mut expected);
+            let ciphertext = aesteve.encrypt_array(plaintext);
+            assert_eq!(ciphertext, <[u8; 16]>::from(expected), "key {:02x?}", key);
+            assert_eq!(aesteve.decrypt_array(ciphertext), plaintext);
+            assert_eq!(aesteve.encrypt_records(&[plaintext; 5])[64..], ciphertext);
+        }
+    }
+
+    #[test]
+    fn test_gmul_tables_match_slow_multiply() {
+        for (matrix, rows) in [(&MIX_MATRIX, &MIX_ROWS), (&INV_MIX_MATRIX, &INV_MIX_ROWS)] {
+            for (coefficients, row) in matrix.iter().zip(rows) {
+                for (&n, &index) in coefficients.iter().zip(row) {
+                    for m in 0..=255u8 {
+                        assert_eq!(gmul(index, m), gf_mul_slow(n, m));
+                    }
+                }
+            }
+        }
+        assert_eq!(table_index(4), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "strict-errors", not(feature = "debug-panics")))]
+    fn test_invalid_round_constant_errors() {
+        for (round, &rc) in RC.iter().enumerate() {
+            assert_eq!(round_constant(round as u8).unwrap(), rc);
+        }
+        assert!(matches!(round_constant(22), Err(AESError::InternalError(what)) if what == "Invalid round: 22"));
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "strict-errors", feature = "debug-panics")))]
+    fn test_invalid_round_constant_falls_back() {
+        assert_eq!(round_constant(22).unwrap(), xtime(0x35));
+    }
+
+    #[test]
+    #[cfg(feature = "debug-panics")]
+    #[should_panic(expected = "Invalid round")]
+    fn test_invalid_round_constant_panics() {
+        let _ = round_constant(22);
+    }
+
+    #[test]
+    fn test_flat_sbox_lookup() {
+        for byte in 0..=255u8 {
+            let (x, y) = ((byte >> 4) as usize, (byte & 15) as usize);
+            assert_eq!(lookup(byte), AES_SBOX[x][y]);
+            assert_eq!(reverse_lookup(byte), REVERSE_AES_SBOX[x][y]);
+            assert_eq!(reverse_lookup(lookup(byte)), byte);
+        }
+    }
+
+    #[test]
+    fn test_is_valid_ciphertext_shape() {
+        let key = [0u8; 16];
+        let aesteve = AESteve::new(&key).unwrap();
+        let encrypted_message = aesteve.encrypt_ecb(String::from("well shaped")).unwrap();
+        assert!(aesteve.is_valid_ciphertext_shape(&encrypted_message));
+
+        assert!(!aesteve.is_valid_ciphertext_shape(""));
+        assert!(!aesteve.is_valid_ciphertext_shape("AAAAAAAAAAAAAAAAAAAA"));
+        assert!(!aesteve.is_valid_ciphertext_shape("not base64 at all!!!"));
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn test_secure_wipe() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let mut plaintext = aesteve.decrypt_bytes(&aesteve.encrypt_bytes(b"sensitive")).unwrap();
+        secure_wipe(&mut plaintext);
+        assert_eq!(plaintext, [0u8; 9]);
+    }
+
+    #[test]
+    fn test_encrypt_both() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap().with_encoding(Encoding::Base64Url);
+        let (raw, encoded) = aesteve.encrypt_both(String::from("hash me, send me")).unwrap();
+        assert_eq!(Encoding::Base64Url.decode(&encoded).unwrap(), raw);
+        assert_eq!(aesteve.decrypt_bytes(&raw).unwrap(), b"hash me, send me");
+        assert_eq!(aesteve.decrypt_ecb(encoded).unwrap(), "hash me, send me");
+    }
+
+    #[test]
+    fn test_aes_ni_enabled() {
+        assert!(!aes_ni_enabled());
+    }
+
+    #[test]
+    fn test_decrypt_lines() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let first = aesteve.encrypt_ecb(String::from("first entry")).unwrap();
+        let second = aesteve.encrypt_ecb(String::from("second entry")).unwrap();
+        let input = format!("{}\nnot base64!\r\n{}\n\nAAAA\n", first, second);
+
+        let results = aesteve.decrypt_lines(&input);
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0].as_ref().unwrap(), "first entry");
+        assert!(matches!(results[1], Err(AESError::InvalidBase64(_))));
+        assert_eq!(results[2].as_ref().unwrap(), "second entry");
+        assert!(matches!(results[3], Err(AESError::CiphertextTooShort)));
+        assert!(matches!(results[4], Err(AESError::CiphertextTooShort)));
+    }
+
+    #[test]
+    fn test_round_key_bytes_fips_197() {
+        // FIPS-197 Appendix A.1.
+        let key = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+        ];
+        let aesteve = AESteve::new(&key).unwrap();
+        assert_eq!(aesteve.round_key_bytes(0), Some(key));
+        assert_eq!(
+            aesteve.round_key_bytes(1),
+            Some([0xa0, 0xfa, 0xfe, 0x17, 0x88, 0x54, 0x2c, 0xb1, 0x23, 0xa3, 0x39, 0x39, 0x2a, 0x6c, 0x76, 0x05])
+        );
+        assert_eq!(
+            aesteve.round_key_bytes(5),
+            Some([0xd4, 0xd1, 0xc6, 0xf8, 0x7c, 0x83, 0x9d, 0x87, 0xca, 0xf2, 0xb8, 0xbc, 0x11, 0xf9, 0x15, 0xbc])
+        );
+        assert_eq!(
+            aesteve.round_key_bytes(10),
+            Some([0xd0, 0x14, 0xf9, 0xa8, 0xc9, 0xee, 0x25, 0x89, 0xe1, 0x3f, 0x0c, 0xc8, 0xb6, 0x63, 0x0c, 0xa6])
+        );
+        assert_eq!(aesteve.round_key_bytes(11), None);
+    }
+
+    #[test]
+    fn test_block_bytes_round_trip() {
+        let bytes: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let block = bytes_to_block(&bytes);
+        assert_eq!(block[1], [4, 5, 6, 7]);
+        assert_eq!(block_to_bytes(&block), bytes);
+        assert_eq!(bytes_to_block(&block_to_bytes(&block)), block);
+    }
+
+    #[test]
+    fn test_verify_decryptable() {
+        let key = [0u8; 16];
+        let aesteve = AESteve::new(&key).unwrap();
+        let encrypted_message = aesteve.encrypt_ecb(String::from("This is a test!")).unwrap();
+        assert!(aesteve.verify_decryptable(&encrypted_message));
+
+        let other = AESteve::new(&[1u8; 16]).unwrap();
+        assert!(!other.verify_decryptable(&encrypted_message));
+        assert!(!aesteve.verify_decryptable("not base64!"));
+
+        let invalid_utf8 = Encoding::Base64.encode(&aesteve.encrypt_with_padding(&[0xFF, 0xFE], &Padding::Iso7816));
+        assert!(!aesteve.verify_decryptable(&invalid_utf8));
+    }
+
+    #[test]
+    fn test_verify_decryptable_misaligned() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let (raw, _) = aesteve.encrypt_both(String::from("This is a longer test message")).unwrap();
+        for len in [4, 17, raw.len() - 1] {
+            let truncated = Encoding::Base64.encode(&raw[..len]);
+            assert!(!aesteve.verify_decryptable(&truncated));
+            assert!(aesteve.decrypt_ecb(truncated).is_err());
+        }
+    }
+
     #[test]
     fn test_invalid_key_length() {
         let key = [0u8; 15];
         let result = AESteve::new(&key);
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), AESError::InvalidKeyLength));
+        for len in [0, 17, 23, 31, 33] {
+            assert!(matches!(AESteve::new(&vec![0u8; len]), Err(AESError::InvalidKeyLength)));
+        }
+    }
+
+    #[test]
+    fn test_key_size_selected_at_runtime() {
+        let ciphers: Vec<AESteve> = [16, 24, 32].iter().map(|&len| AESteve::new(&vec![7u8; len]).unwrap()).collect();
+        let rounds: Vec<usize> = ciphers.iter().map(AESteve::rounds).collect();
+        assert_eq!(rounds, [10, 12, 14]);
+
+        let encrypted: Vec<String> = ciphers.iter().map(|cipher| cipher.encrypt_ecb(String::from("any size")).unwrap()).collect();
+        assert_ne!(encrypted[0], encrypted[1]);
+        assert_ne!(encrypted[1], encrypted[2]);
+        for (cipher, encrypted_message) in ciphers.iter().zip(encrypted) {
+            assert_eq!(cipher.decrypt_ecb(encrypted_message).unwrap(), "any size");
+        }
+    }
+
+    #[test]
+    fn test_encrypt_array_aes192_fips_197() {
+        // FIPS-197, appendix C.2.
+        let key: Vec<u8> = (0u8..24).collect();
+        let aesteve = AESteve::new(&key).unwrap();
+        assert_eq!(aesteve.variant(), AesVariant::Aes192);
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+        let ciphertext = [0xdd, 0xa9, 0x7c, 0xa4, 0x86, 0x4c, 0xdf, 0xe0, 0x6e, 0xaf, 0x70, 0xa0, 0xec, 0x0d, 0x71, 0x91];
+        assert_eq!(aesteve.encrypt_array(plaintext), ciphertext);
+        assert_eq!(aesteve.decrypt_array(ciphertext), plaintext);
+        assert!(aesteve.round_key_bytes(12).is_some());
+        assert_eq!(aesteve.round_key_bytes(13), None);
+    }
+
+    #[test]
+    fn test_encrypt_array_aes256_fips_197() {
+        // FIPS-197, appendix C.3.
+        let key: Vec<u8> = (0u8..32).collect();
+        let aesteve = AESteve::new(&key).unwrap();
+        assert_eq!(aesteve.variant(), AesVariant::Aes256);
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+        let ciphertext = [0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf, 0xea, 0xfc, 0x49, 0x90, 0x4b, 0x49, 0x60, 0x89];
+        assert_eq!(aesteve.encrypt_array(plaintext), ciphertext);
+        assert_eq!(aesteve.decrypt_array(ciphertext), plaintext);
+        assert!(aesteve.round_key_bytes(14).is_some());
+        assert_eq!(aesteve.round_key_bytes(15), None);
+
+        let message = "a message spanning three AES blocks in all";
+        let encrypted_message = aesteve.encrypt_ecb(message.to_string()).unwrap();
+        assert_eq!(aesteve.decrypt_ecb(encrypted_message).unwrap(), message);
+        assert_eq!(AESteve::new(&[0u8; 16]).unwrap().variant(), AesVariant::Aes128);
     }
 }
\ No newline at end of file
@@ -0,0 +1,144 @@
+use crate::{AESError, AESteve, Padding, PaddingScheme};
+use memmap2::{Mmap, MmapMut};
+use rayon::prelude::*;
+use std::fs::File;
+use std::path::Path;
+
+/// Creates (or truncates) `path` at exactly `len` bytes and maps it writable.
+fn map_output(path: &Path, len: usize) -> Result<(File, MmapMut), AESError> {
+    let file = File::options().read(true).write(true).create(true).truncate(true).open(path)?;
+    file.set_len(len as u64)?;
+    // SAFETY: the file was just created or truncated by us; see the note on `encrypt_mmap`.
+    let map = unsafe { MmapMut::map_mut(&file)? };
+    Ok((file, map))
+}
+
+impl AESteve {
+    /// Encrypts the file at `input` into `output` through memory maps, in ECB mode with
+    /// ISO/IEC 7816-4 padding.
+    ///
+    /// The input is mapped read-only and copied straight into the mapped output, which is
+    /// then encrypted in place in parallel: no read or write syscalls per chunk and no
+    /// intermediate buffers, so very large files go faster than through a streaming reader.
+    /// The output is the same as [`AESteve::encrypt_with_padding`] with
+    /// [`Padding::Iso7816`]; builder settings such as `with_checksum` and `with_envelope`
+    /// do not apply.
+    ///
+    /// Neither file may be modified by another process while this runs; a file changing
+    /// under a memory map is undefined behaviour.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The file to encrypt.
+    /// * `output` - The file to write the ciphertext to. Created or truncated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::Io` if either file cannot be opened, sized or mapped.
+    pub fn encrypt_mmap(&self, input: &Path, output: &Path) -> Result<(), AESError> {
+        let source = File::open(input)?;
+        // SAFETY: the map is only read, and the caller guarantees the file is not modified.
+        let plaintext = unsafe { Mmap::map(&source)? };
+        let padded_len = plaintext.len() / 16 * 16 + 16;
+
+        let (_file, mut ciphertext) = map_output(output, padded_len)?;
+        ciphertext[..plaintext.len()].copy_from_slice(&plaintext);
+        ciphertext[plaintext.len()] = 0x80;
+
+        let task_len = self.blocks_per_task(padded_len / 16) * 16;
+        ciphertext.par_chunks_mut(task_len).for_each(|task| self.encrypt_run(task));
+        ciphertext.flush()?;
+        Ok(())
+    }
+
+    /// Decrypts a file written by [`AESteve::encrypt_mmap`] into `output` through memory maps.
+    ///
+    /// If the padding turns out to be invalid, `output` is truncated to zero bytes so no
+    /// undecodable plaintext is left behind.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The file to decrypt.
+    /// * `output` - The file to write the plaintext to. Created or truncated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::Io` if either file cannot be opened, sized or mapped,
+    /// `AESError::CiphertextTooShort` if `input` is shorter than one block,
+    /// `AESError::InvalidCiphertextLength` if it is not a whole number of blocks, or
+    /// `AESError::InvalidPadding` if the decrypted data is not validly padded.
+    pub fn decrypt_mmap(&self, input: &Path, output: &Path) -> Result<(), AESError> {
+        let source = File::open(input)?;
+        // SAFETY: the map is only read, and the caller guarantees the file is not modified.
+        let ciphertext = unsafe { Mmap::map(&source)? };
+        if ciphertext.len() < Self::min_ciphertext_len() {
+            return Err(AESError::CiphertextTooShort);
+        }
+        if !ciphertext.len().is_multiple_of(16) {
+            return Err(AESError::InvalidCiphertextLength);
+        }
+
+        let (file, mut plaintext) = map_output(output, ciphertext.len())?;
+        plaintext.copy_from_slice(&ciphertext);
+        plaintext.par_chunks_mut(16).for_each(|chunk| {
+            let block: &mut [u8; 16] = chunk.try_into().unwrap();
+            *block = self.decrypt_block_bytes(block);
+        });
+
+        let last_block = plaintext.len() - 16;
+        let plaintext_len = Padding::Iso7816.depad(plaintext[last_block..].to_vec()).map(|tail| last_block + tail.len());
+        drop(plaintext);
+        file.set_len(plaintext_len.as_ref().map_or(0, |&len| len as u64))?;
+        plaintext_len.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aesteve-mmap-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_mmap_round_trip() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let (plain, cipher, decrypted) = (temp_path("plain"), temp_path("cipher"), temp_path("decrypted"));
+        for len in [0, 15, 16, 3 * 1024 * 1024 + 5] {
+            let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            fs::write(&plain, &data).unwrap();
+
+            aesteve.encrypt_mmap(&plain, &cipher).unwrap();
+            let ciphertext = fs::read(&cipher).unwrap();
+            assert_eq!(ciphertext, aesteve.encrypt_with_padding(&data, &Padding::Iso7816));
+            aesteve.clone().with_chunk_size(usize::MAX).encrypt_mmap(&plain, &cipher).unwrap();
+            assert_eq!(fs::read(&cipher).unwrap(), ciphertext);
+
+            aesteve.decrypt_mmap(&cipher, &decrypted).unwrap();
+            assert_eq!(fs::read(&decrypted).unwrap(), data);
+        }
+        for path in [plain, cipher, decrypted] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_decrypt_mmap_rejects_bad_input() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let (cipher, decrypted) = (temp_path("bad-cipher"), temp_path("bad-decrypted"));
+        fs::write(&cipher, [0u8; 24]).unwrap();
+        assert!(matches!(aesteve.decrypt_mmap(&cipher, &decrypted), Err(AESError::InvalidCiphertextLength)));
+
+        fs::write(&cipher, aesteve.encrypt_array([0u8; 16])).unwrap();
+        assert!(matches!(aesteve.decrypt_mmap(&cipher, &decrypted), Err(AESError::InvalidPadding)));
+        assert_eq!(fs::metadata(&decrypted).unwrap().len(), 0);
+
+        assert!(matches!(aesteve.decrypt_mmap(&temp_path("missing"), &decrypted), Err(AESError::Io(_))));
+        for path in [cipher, decrypted] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
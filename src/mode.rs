@@ -0,0 +1,136 @@
+use crate::{AESError, AESteve, Padding};
+
+/// A block cipher mode of operation, built on the public single-block primitives.
+///
+/// Implement this to plug a mode the crate does not ship into
+/// [`AESteve::encrypt_with_mode`] and [`AESteve::decrypt_with_mode`]. Per-message
+/// parameters such as an IV or nonce belong in the implementing type.
+pub trait CipherMode {
+    /// Encrypts `data` under `cipher`.
+    fn encrypt(&self, cipher: &AESteve, data: &[u8]) -> Vec<u8>;
+
+    /// Decrypts output of [`CipherMode::encrypt`] under `cipher`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError` if `data` is not valid ciphertext for this mode.
+    fn decrypt(&self, cipher: &AESteve, data: &[u8]) -> Result<Vec<u8>, AESError>;
+}
+
+/// Built-in modes of operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// ECB with ISO/IEC 7816-4 padding, as [`AESteve::encrypt_with_padding`].
+    Ecb,
+    /// CBC with PKCS#7 padding under the given IV, as [`AESteve::encrypt_cbc`].
+    Cbc([u8; 16]),
+    /// CTR with the given 128-bit initial counter, as [`AESteve::encrypt_ctr`].
+    Ctr([u8; 16]),
+}
+
+impl CipherMode for Mode {
+    fn encrypt(&self, cipher: &AESteve, data: &[u8]) -> Vec<u8> {
+        match self {
+            Mode::Ecb => cipher.encrypt_with_padding(data, &Padding::Iso7816),
+            Mode::Cbc(iv) => cipher.encrypt_cbc(data, iv),
+            Mode::Ctr(initial_counter) => cipher.encrypt_ctr(data, initial_counter),
+        }
+    }
+
+    fn decrypt(&self, cipher: &AESteve, data: &[u8]) -> Result<Vec<u8>, AESError> {
+        match self {
+            Mode::Ecb => cipher.decrypt_with_padding(data, &Padding::Iso7816),
+            Mode::Cbc(iv) => cipher.decrypt_cbc(data, iv),
+            Mode::Ctr(initial_counter) => Ok(cipher.encrypt_ctr(data, initial_counter)),
+        }
+    }
+}
+
+impl AESteve {
+    /// Encrypts the given bytes with `mode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted.
+    /// * `mode` - The mode of operation, built in ([`Mode`]) or custom.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext.
+    pub fn encrypt_with_mode<M: CipherMode + ?Sized>(&self, data: &[u8], mode: &M) -> Vec<u8> {
+        mode.encrypt(self, data)
+    }
+
+    /// Decrypts raw ciphertext produced by [`AESteve::encrypt_with_mode`] with the same `mode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The raw ciphertext.
+    /// * `mode` - The mode the ciphertext was produced with.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns whatever error `mode` reports for invalid ciphertext.
+    pub fn decrypt_with_mode<M: CipherMode + ?Sized>(&self, ciphertext: &[u8], mode: &M) -> Result<Vec<u8>, AESError> {
+        mode.decrypt(self, ciphertext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unpadded ECB over whole blocks, built only from the public block primitives.
+    struct RawEcb;
+
+    impl CipherMode for RawEcb {
+        fn encrypt(&self, cipher: &AESteve, data: &[u8]) -> Vec<u8> {
+            data.chunks_exact(16)
+                .flat_map(|block| cipher.encrypt_array(block.try_into().unwrap()))
+                .collect()
+        }
+
+        fn decrypt(&self, cipher: &AESteve, data: &[u8]) -> Result<Vec<u8>, AESError> {
+            if !data.len().is_multiple_of(16) {
+                return Err(AESError::InvalidCiphertextLength);
+            }
+            let mut output = vec![0u8; data.len()];
+            for (input, out) in data.chunks_exact(16).zip(output.chunks_exact_mut(16)) {
+                cipher.decrypt_block_into(input.try_into().unwrap(), out.try_into().unwrap());
+            }
+            Ok(output)
+        }
+    }
+
+    #[test]
+    fn test_custom_mode_round_trip() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let data = [0x5au8; 48];
+        let ciphertext = aesteve.encrypt_with_mode(&data, &RawEcb);
+        assert_eq!(ciphertext.len(), 48);
+        assert_eq!(ciphertext[..16], aesteve.encrypt_array([0x5a; 16]));
+        assert_eq!(aesteve.decrypt_with_mode(&ciphertext, &RawEcb).unwrap(), data);
+
+        let dynamic: &dyn CipherMode = &RawEcb;
+        assert!(matches!(aesteve.decrypt_with_mode(&ciphertext[..20], dynamic), Err(AESError::InvalidCiphertextLength)));
+    }
+
+    #[test]
+    fn test_builtin_modes_match_direct_calls() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let data = b"plugged into the generic API";
+        let iv = [7u8; 16];
+
+        assert_eq!(aesteve.encrypt_with_mode(data, &Mode::Ecb), aesteve.encrypt_with_padding(data, &Padding::Iso7816));
+        assert_eq!(aesteve.encrypt_with_mode(data, &Mode::Cbc(iv)), aesteve.encrypt_cbc(data, &iv));
+        assert_eq!(aesteve.encrypt_with_mode(data, &Mode::Ctr(iv)), aesteve.encrypt_ctr(data, &iv));
+        for mode in [Mode::Ecb, Mode::Cbc(iv), Mode::Ctr(iv)] {
+            let ciphertext = aesteve.encrypt_with_mode(data, &mode);
+            assert_eq!(aesteve.decrypt_with_mode(&ciphertext, &mode).unwrap(), data);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::AESteve;
+
+impl AESteve {
+    /// Generates `len` bytes of OFB keystream starting from `iv`.
+    ///
+    /// The first keystream block is the encryption of `iv`, and each following block the
+    /// encryption of the block before it, so the keystream depends only on the key and
+    /// `iv`, never on the data. The last block is truncated when `len` is not a multiple
+    /// of 16. Generation is inherently sequential.
+    ///
+    /// # Arguments
+    ///
+    /// * `iv` - The 16-byte initialization vector. Never reuse one under the same key.
+    /// * `len` - The number of keystream bytes to produce.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The keystream.
+    pub fn keystream(&self, iv: &[u8; 16], len: usize) -> Vec<u8> {
+        let mut keystream = Vec::with_capacity(len.next_multiple_of(16));
+        let mut block = *iv;
+        while keystream.len() < len {
+            block = self.encrypt_block_bytes(&block);
+            keystream.extend_from_slice(&block);
+        }
+        keystream.truncate(len);
+        keystream
+    }
+
+    /// Encrypts (or decrypts) `data` in OFB mode, XORing it with [`AESteve::keystream`].
+    ///
+    /// No padding is applied, so the output is as long as `data`, and OFB is symmetric:
+    /// the same call decrypts.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted or decrypted.
+    /// * `iv` - The 16-byte initialization vector. Never reuse one under the same key.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The transformed bytes.
+    pub fn encrypt_ofb(&self, data: &[u8], iv: &[u8; 16]) -> Vec<u8> {
+        let mut output = self.keystream(iv, data.len());
+        for (byte, &input) in output.iter_mut().zip(data) {
+            *byte ^= input;
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ofb_sp800_38a() {
+        // NIST SP 800-38A, F.4.1 (OFB-AES128.Encrypt), first two blocks.
+        let key = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+        let iv = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f];
+        let plaintext = [
+            0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+            0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+        ];
+        let ciphertext = [
+            0x3b, 0x3f, 0xd9, 0x2e, 0xb7, 0x2d, 0xad, 0x20, 0x33, 0x34, 0x49, 0xf8, 0xe8, 0x3c, 0xfb, 0x4a,
+            0x77, 0x89, 0x50, 0x8d, 0x16, 0x91, 0x8f, 0x03, 0xf5, 0x3c, 0x52, 0xda, 0xc5, 0x4e, 0xd8, 0x25,
+        ];
+        let aesteve = AESteve::new(&key).unwrap();
+        assert_eq!(aesteve.encrypt_ofb(&plaintext, &iv), ciphertext);
+        assert_eq!(aesteve.encrypt_ofb(&ciphertext, &iv), plaintext);
+        assert_eq!(aesteve.encrypt_ofb(&plaintext[..21], &iv), ciphertext[..21]);
+    }
+
+    #[test]
+    fn test_keystream_truncates() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let iv = [9u8; 16];
+        let full = aesteve.keystream(&iv, 48);
+        assert_eq!(&full[..16], aesteve.encrypt_array(iv));
+        assert_eq!(&full[16..32], aesteve.encrypt_array(full[..16].try_into().unwrap()));
+        for len in [0, 1, 15, 17, 47] {
+            assert_eq!(aesteve.keystream(&iv, len), full[..len]);
+        }
+    }
+}
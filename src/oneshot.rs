@@ -0,0 +1,52 @@
+use crate::{AESError, AESteve};
+
+/// Encrypts `data` in ECB mode under `key` without keeping an instance around.
+///
+/// Expands the key, encrypts with the default padding, and zeroes the key schedule
+/// before returning. Meant for short-lived handlers that would otherwise build and
+/// discard an `AESteve` per request.
+///
+/// # Arguments
+///
+/// * `key` - The 128-bit key.
+/// * `data` - The bytes to be encrypted.
+///
+/// # Returns
+///
+/// * `Vec<u8>` - The raw ciphertext.
+pub fn aes128_ecb_encrypt(key: &[u8; 16], data: &[u8]) -> Vec<u8> {
+    let cipher = AESteve::new(key).expect("a 16-byte key is always valid");
+    cipher.encrypt_bytes(data)
+}
+
+/// Decrypts output of [`aes128_ecb_encrypt`], zeroing the key schedule before returning.
+///
+/// # Arguments
+///
+/// * `key` - The 128-bit key.
+/// * `ciphertext` - The raw ciphertext.
+///
+/// # Returns
+///
+/// * `Vec<u8>` - The decrypted bytes.
+///
+/// # Errors
+///
+/// Returns `AESError` if the ciphertext is too short or not validly padded.
+pub fn aes128_ecb_decrypt(key: &[u8; 16], ciphertext: &[u8]) -> Result<Vec<u8>, AESError> {
+    let cipher = AESteve::new(key).expect("a 16-byte key is always valid");
+    cipher.decrypt_bytes(ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oneshot_round_trip() {
+        let key = [0x42u8; 16];
+        let ciphertext = aes128_ecb_encrypt(&key, b"stateless handler");
+        assert_eq!(ciphertext, AESteve::new(&key).unwrap().encrypt_bytes(b"stateless handler"));
+        assert_eq!(aes128_ecb_decrypt(&key, &ciphertext).unwrap(), b"stateless handler");
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{AESError, AESteve};
+
+/// A scheme for filling a message up to a whole number of 16-byte blocks.
+pub trait PaddingScheme {
+    /// Appends padding to `message` so its length is a nonzero multiple of 16.
+    fn pad(&self, message: &mut Vec<u8>);
+
+    /// Removes the padding appended by [`PaddingScheme::pad`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidPadding` if `message` does not end in valid padding.
+    fn depad(&self, message: Vec<u8>) -> Result<Vec<u8>, AESError>;
+}
+
+/// Built-in padding schemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// ISO/IEC 7816-4: a `0x80` marker followed by `0x00` bytes. Used by `encrypt`/`decrypt`.
+    ///
+    /// Removal only looks at the final block, where the trailing `0x00` bytes must follow a
+    /// `0x80` marker, so `0x80` bytes in the plaintext are never mistaken for padding.
+    Iso7816,
+    /// PKCS#7: `n` bytes each holding the value `n`.
+    Pkcs7,
+    /// ANSI X9.23: `0x00` bytes followed by a final byte holding the padding length.
+    AnsiX923,
+}
+
+impl PaddingScheme for Padding {
+    fn pad(&self, message: &mut Vec<u8>) {
+        match self {
+            Padding::Iso7816 => {
+                message.push(0x80);
+                while !message.len().is_multiple_of(16) {
+                    message.push(0x00);
+                }
+            }
+            Padding::Pkcs7 => {
+                let pad_len = 16 - message.len() % 16;
+                message.resize(message.len() + pad_len, pad_len as u8);
+            }
+            Padding::AnsiX923 => {
+                let pad_len = 16 - message.len() % 16;
+                message.resize(message.len() + pad_len - 1, 0x00);
+                message.push(pad_len as u8);
+            }
+        }
+    }
+
+    fn depad(&self, mut message: Vec<u8>) -> Result<Vec<u8>, AESError> {
+        match self {
+            Padding::Iso7816 => {
+                // The marker always lies in the final block; a match further back would be
+                // plaintext, so an all-zero final block is rejected rather than searched past.
+                let last_block = message.len().saturating_sub(16);
+                let pos = message[last_block..]
+                    .iter()
+                    .rposition(|&n| n != 0x00)
+                    .map(|pos| last_block + pos)
+                    .ok_or(AESError::InvalidPadding)?;
+                if message[pos] != 0x80 {
+                    return Err(AESError::InvalidPadding);
+                }
+                message.truncate(pos);
+                Ok(message)
+            }
+            Padding::Pkcs7 => {
+                let pad_len = *message.last().ok_or(AESError::InvalidPadding)? as usize;
+                if pad_len == 0 || pad_len > 16 || pad_len > message.len() {
+                    return Err(AESError::InvalidPadding);
+                }
+                let start = message.len() - pad_len;
+                if message[start..].iter().any(|&n| n as usize != pad_len) {
+                    return Err(AESError::InvalidPadding);
+                }
+                message.truncate(start);
+                Ok(message)
+            }
+            Padding::AnsiX923 => {
+                let pad_len = *message.last().ok_or(AESError::InvalidPadding)? as usize;
+                if pad_len == 0 || pad_len > 16 || pad_len > message.len() {
+                    return Err(AESError::InvalidPadding);
+                }
+                let start = message.len() - pad_len;
+                if message[start..message.len() - 1].iter().any(|&n| n != 0x00) {
+                    return Err(AESError::InvalidPadding);
+                }
+                message.truncate(start);
+                Ok(message)
+            }
+        }
+    }
+}
+
+impl AESteve {
+    /// Decrypts the final ECB block of `ciphertext` and returns its padding bytes unstripped.
+    ///
+    /// A debugging aid for interop: shows which padding a foreign system used. The block
+    /// is checked against PKCS#7, ANSI X9.23 and ISO/IEC 7816-4 in that order and the
+    /// padding of the first scheme that matches is returned. A single `0x01` byte is
+    /// valid PKCS#7 and ANSI X9.23 alike.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The raw ciphertext.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The trailing padding bytes of the final block.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::CiphertextTooShort` if `ciphertext` is shorter than one block, or
+    /// `AESError::InvalidPadding` if the final block matches none of the built-in schemes.
+    pub fn inspect_padding(&self, ciphertext: &[u8]) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < Self::min_ciphertext_len() {
+            return Err(AESError::CiphertextTooShort);
+        }
+        let last_block = &ciphertext[ciphertext.len() - 16..];
+        let block = self.decrypt_block_bytes(last_block.try_into().unwrap());
+        [Padding::Pkcs7, Padding::AnsiX923, Padding::Iso7816]
+            .iter()
+            .find_map(|scheme| scheme.depad(block.to_vec()).ok())
+            .map(|stripped| block[stripped.len()..].to_vec())
+            .ok_or(AESError::InvalidPadding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(padding: Padding) {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        for len in 0..40 {
+            let message: Vec<u8> = (0..len as u8).collect();
+            let encrypted = aesteve.encrypt_with_padding(&message, &padding);
+            assert!(encrypted.len().is_multiple_of(16) && encrypted.len() > message.len());
+            assert_eq!(aesteve.decrypt_with_padding(&encrypted, &padding).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_iso7816_round_trip() {
+        round_trip(Padding::Iso7816);
+    }
+
+    #[test]
+    fn test_iso7816_plaintext_contains_marker() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        for message in [vec![0x80u8; 16], vec![0x41, 0x80, 0x00, 0x00, 0x42], vec![0x80, 0x00, 0x00]] {
+            let encrypted = aesteve.encrypt_with_padding(&message, &Padding::Iso7816);
+            assert_eq!(aesteve.decrypt_with_padding(&encrypted, &Padding::Iso7816).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_iso7816_all_zero_final_block() {
+        let mut message = vec![0x41u8; 15];
+        message.push(0x80);
+        message.extend_from_slice(&[0x00; 16]);
+        assert!(matches!(Padding::Iso7816.depad(message), Err(AESError::InvalidPadding)));
+    }
+
+    #[test]
+    fn test_pkcs7_round_trip() {
+        round_trip(Padding::Pkcs7);
+    }
+
+    #[test]
+    fn test_pkcs7_pad() {
+        let mut message = vec![0xAAu8; 13];
+        Padding::Pkcs7.pad(&mut message);
+        assert_eq!(&message[13..], &[3, 3, 3]);
+    }
+
+    #[test]
+    fn test_ansi_x923_round_trip() {
+        round_trip(Padding::AnsiX923);
+    }
+
+    #[test]
+    fn test_ansi_x923_vector() {
+        let key: Vec<u8> = (0u8..16).collect();
+        let aesteve = AESteve::new(&key).unwrap();
+        let message = [0xDDu8; 12];
+
+        let mut padded = message.to_vec();
+        Padding::AnsiX923.pad(&mut padded);
+        assert_eq!(&padded[12..], &[0x00, 0x00, 0x00, 0x04]);
+
+        let expected = [
+            0x90, 0x75, 0x29, 0x78, 0x1a, 0xa2, 0x38, 0xc8,
+            0x81, 0x12, 0x49, 0xc5, 0xae, 0xbd, 0x76, 0xaa,
+        ];
+        assert_eq!(aesteve.encrypt_with_padding(&message, &Padding::AnsiX923), expected);
+        assert_eq!(aesteve.decrypt_with_padding(&expected, &Padding::AnsiX923).unwrap(), message);
+    }
+
+    #[test]
+    fn test_ansi_x923_nonzero_fill() {
+        let mut message = vec![0xDDu8; 12];
+        message.extend_from_slice(&[0x00, 0x01, 0x00, 0x04]);
+        assert!(matches!(Padding::AnsiX923.depad(message), Err(AESError::InvalidPadding)));
+    }
+
+    #[test]
+    fn test_invalid_padding() {
+        let message = vec![0x41u8; 16];
+        assert!(matches!(Padding::Iso7816.depad(message.clone()), Err(AESError::InvalidPadding)));
+        assert!(matches!(Padding::Pkcs7.depad(message.clone()), Err(AESError::InvalidPadding)));
+        assert!(matches!(Padding::AnsiX923.depad(message), Err(AESError::InvalidPadding)));
+    }
+
+    #[test]
+    fn test_inspect_padding() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        for padding in [Padding::Iso7816, Padding::Pkcs7, Padding::AnsiX923] {
+            for len in [0, 5, 15, 16, 31] {
+                let message = vec![0x41u8; len];
+                let mut padded = message.clone();
+                padding.pad(&mut padded);
+                let encrypted = aesteve.encrypt_with_padding(&message, &padding);
+                assert_eq!(aesteve.inspect_padding(&encrypted).unwrap(), &padded[len..]);
+            }
+        }
+
+        let unpadded = aesteve.encrypt_block_bytes(&[0x41u8; 16]);
+        assert!(matches!(aesteve.inspect_padding(&unpadded), Err(AESError::InvalidPadding)));
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{AESError, AESteve, Padding};
+
+/// Reverses the order of the 16-byte blocks in `data`, leaving each block intact.
+fn reverse_blocks(data: &mut [u8]) {
+    let blocks = data.as_chunks_mut::<16>().0;
+    blocks.reverse();
+}
+
+impl AESteve {
+    /// Legacy-compatibility shim: encrypts `message` in ECB mode and emits the ciphertext
+    /// blocks last-block-first.
+    ///
+    /// Some legacy file formats store ECB blocks in reverse order. Each block is
+    /// encrypted exactly as by [`AESteve::encrypt_with_padding`] with ISO/IEC 7816-4
+    /// padding; only their order in the output changes. Do not use it for new data.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The bytes to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext, final block first.
+    pub fn encrypt_reversed(&self, message: &[u8]) -> Vec<u8> {
+        let mut ciphertext = self.encrypt_with_padding(message, &Padding::Iso7816);
+        reverse_blocks(&mut ciphertext);
+        ciphertext
+    }
+
+    /// Decrypts output of [`AESteve::encrypt_reversed`], consuming blocks last-block-first.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The raw ciphertext, final block first.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::CiphertextTooShort` if `ciphertext` is shorter than one block,
+    /// `AESError::InvalidCiphertextLength` if it is not a whole number of blocks, or
+    /// `AESError::InvalidPadding` if the decrypted bytes are not validly padded.
+    pub fn decrypt_reversed(&self, ciphertext: &[u8]) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < Self::min_ciphertext_len() {
+            return Err(AESError::CiphertextTooShort);
+        }
+        if !ciphertext.len().is_multiple_of(16) {
+            return Err(AESError::InvalidCiphertextLength);
+        }
+        let mut ordered = ciphertext.to_vec();
+        reverse_blocks(&mut ordered);
+        self.decrypt_with_padding(&ordered, &Padding::Iso7816)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reversed_round_trip() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        for message in [&b""[..], b"one block", b"a message spanning three AES blocks in all"] {
+            let ciphertext = aesteve.encrypt_reversed(message);
+            assert_eq!(aesteve.decrypt_reversed(&ciphertext).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_reversed_matches_manual_reversal() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let message = b"a message spanning three AES blocks in all";
+        let normal = aesteve.encrypt_bytes(message);
+        let manual: Vec<u8> = normal.chunks(16).rev().flatten().copied().collect();
+        assert_eq!(aesteve.encrypt_reversed(message), manual);
+        assert_eq!(aesteve.decrypt_reversed(&manual).unwrap(), message);
+    }
+
+    #[test]
+    fn test_decrypt_reversed_bad_length() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        assert!(matches!(aesteve.decrypt_reversed(&[0u8; 8]), Err(AESError::CiphertextTooShort)));
+        assert!(matches!(aesteve.decrypt_reversed(&[0u8; 24]), Err(AESError::InvalidCiphertextLength)));
+    }
+}
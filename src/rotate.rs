@@ -0,0 +1,49 @@
+use crate::{AESError, AESteve};
+
+impl AESteve {
+    /// Re-encrypts ciphertext from this key under `new_cipher`, for key rotation.
+    ///
+    /// Decrypts with [`AESteve::decrypt_bytes`] and encrypts with `new_cipher`'s
+    /// [`AESteve::encrypt_bytes`], so each side's checksum and envelope settings apply.
+    /// The plaintext never leaves this method. With the `zeroize` feature, the
+    /// intermediate plaintext buffer is zeroed before returning, whether or not
+    /// re-encryption happened; temporary copies made inside decryption are not.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_cipher` - The cipher holding the new key.
+    /// * `ciphertext` - The raw ciphertext under this key.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The raw ciphertext under the new key.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error `decrypt_bytes` returns for `ciphertext`.
+    pub fn rotate(&self, new_cipher: &AESteve, ciphertext: &[u8]) -> Result<Vec<u8>, AESError> {
+        #[allow(unused_mut)]
+        let mut plaintext = self.decrypt_bytes(ciphertext)?;
+        let rotated = new_cipher.encrypt_bytes(&plaintext);
+        #[cfg(feature = "zeroize")]
+        crate::secure_wipe(&mut plaintext);
+        Ok(rotated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate() {
+        let old = AESteve::new(&[1u8; 16]).unwrap();
+        let new = AESteve::new(&[2u8; 16]).unwrap();
+        let ciphertext = old.encrypt_bytes(b"rotate me to the new key");
+
+        let rotated = old.rotate(&new, &ciphertext).unwrap();
+        assert_eq!(new.decrypt_bytes(&rotated).unwrap(), b"rotate me to the new key");
+        assert!(old.decrypt_bytes(&rotated).map_or(true, |plaintext| plaintext != b"rotate me to the new key"));
+        assert!(matches!(old.rotate(&new, &ciphertext[..8]), Err(AESError::CiphertextTooShort)));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{wipe, AESError, AESteve, Padding, PaddingScheme};
+use rayon::prelude::*;
+use std::fmt;
+
+/// Decrypted plaintext that is zeroed when dropped.
+///
+/// The bytes are only reachable through [`Secret::expose`], so every read of the plaintext
+/// is explicit at the call site, and `Debug` prints a placeholder instead of the contents.
+/// Copies the caller makes from `expose` are not covered.
+pub struct Secret(Vec<u8>);
+
+impl Secret {
+    /// Returns the plaintext bytes.
+    pub fn expose(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn zeroize(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret([REDACTED; {}])", self.0.len())
+    }
+}
+
+impl AESteve {
+    /// Decrypts the given ECB-encrypted message like [`AESteve::decrypt_ecb`] into a
+    /// [`Secret`] that zeroes the plaintext when dropped.
+    ///
+    /// Blocks are decrypted in place in a single buffer that is owned by the `Secret` from
+    /// the start, so no unwiped copy of the plaintext is left behind, even on error.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted_message` - The encrypted message in the configured encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `Secret` - The decrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::CiphertextTooShort` or `AESError::InvalidCiphertextLength` if the
+    /// ciphertext is not a whole, nonzero number of blocks, `AESError::InvalidPadding` if
+    /// the decrypted bytes are not validly padded, or any error decoding the message.
+    pub fn decrypt_secret(&self, encrypted_message: &str) -> Result<Secret, AESError> {
+        let decoded_message = self.decode_ciphertext(encrypted_message)?;
+        let payload = self.open_envelope(&decoded_message)?;
+        if payload.len() < Self::min_ciphertext_len() {
+            return Err(AESError::CiphertextTooShort);
+        }
+        if !payload.len().is_multiple_of(16) {
+            return Err(AESError::InvalidCiphertextLength);
+        }
+
+        let mut secret = Secret(payload.to_vec());
+        secret.0.par_chunks_mut(16).for_each(|chunk| {
+            let block: &mut [u8; 16] = chunk.try_into().unwrap();
+            *block = self.decrypt_block_bytes(block);
+        });
+
+        let last_block = secret.0.len() - 16;
+        let mut tail = Padding::Iso7816.depad(secret.0[last_block..].to_vec())?;
+        let plaintext_len = last_block + tail.len();
+        wipe(&mut tail);
+        let payload_len = self.verify_checksum(&secret.0[..plaintext_len])?;
+        wipe(&mut secret.0[payload_len..]);
+        secret.0.truncate(payload_len);
+        Ok(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Encoding;
+
+    #[test]
+    fn test_decrypt_secret() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap().with_checksum(true);
+        let encrypted_message = aesteve.encrypt_ecb(String::from("hunter2 and then some")).unwrap();
+        let secret = aesteve.decrypt_secret(&encrypted_message).unwrap();
+        assert_eq!(secret.expose(), b"hunter2 and then some");
+        assert_eq!(format!("{:?}", secret), "Secret([REDACTED; 21])");
+
+        assert!(matches!(aesteve.decrypt_secret("AAAA"), Err(AESError::CiphertextTooShort)));
+
+        let (raw, _) = aesteve.encrypt_both(String::from("hunter2 and then some")).unwrap();
+        let truncated = Encoding::Base64.encode(&raw[..20]);
+        assert!(matches!(aesteve.decrypt_secret(&truncated), Err(AESError::InvalidCiphertextLength)));
+        assert!(matches!(aesteve.decrypt_ecb(truncated), Err(AESError::InvalidCiphertextLength)));
+    }
+
+    #[test]
+    fn test_secret_zeroized() {
+        // Reading the buffer after the real drop would be use-after-free, so check the
+        // wipe that `Drop` performs on a live value instead.
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let encrypted_message = aesteve.encrypt_ecb(String::from("hunter2")).unwrap();
+        let mut secret = aesteve.decrypt_secret(&encrypted_message).unwrap();
+        secret.zeroize();
+        assert_eq!(secret.expose(), [0u8; 7]);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{random_array, wipe, AESError, AESteve};
+use pbkdf2::pbkdf2_hmac;
+use sha2::Sha256;
+
+const MAGIC: &[u8; 4] = b"AESP";
+/// Version 1: PBKDF2-HMAC-SHA256 with `PBKDF2_ROUNDS`, AES-128-GCM.
+const VERSION: u8 = 1;
+/// OWASP's 2023 recommendation for PBKDF2-HMAC-SHA256.
+const PBKDF2_ROUNDS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const HEADER_LEN: usize = MAGIC.len() + 1 + SALT_LEN;
+
+fn password_cipher(password: &str, salt: &[u8]) -> AESteve {
+    let mut key = [0u8; 16];
+    pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    let cipher = AESteve::new(&key).unwrap();
+    wipe(&mut key);
+    cipher
+}
+
+/// Encrypts `plaintext` under a password into a self-describing blob.
+///
+/// Derives the key from `password` and a random salt with PBKDF2-HMAC-SHA256, then
+/// encrypts with AES-128-GCM under a random nonce. The blob is
+/// `magic ‖ version ‖ salt ‖ nonce ‖ ciphertext ‖ tag`; the magic, version and salt are
+/// authenticated as associated data. Every parameter is fixed by the version byte, so
+/// there is nothing to configure or get wrong.
+///
+/// # Arguments
+///
+/// * `password` - The password to protect the data with.
+/// * `plaintext` - The data to be encrypted.
+///
+/// # Returns
+///
+/// * `Vec<u8>` - The sealed blob.
+///
+/// # Panics
+///
+/// Panics if the operating system's random number generator is unavailable, or if
+/// `plaintext` is longer than AES-GCM allows (`2^32 - 2` blocks).
+pub fn seal_with_password(password: &str, plaintext: &[u8]) -> Vec<u8> {
+    let random: [u8; SALT_LEN + NONCE_LEN] = random_array();
+    let (salt, nonce) = random.split_at(SALT_LEN);
+
+    let mut blob = Vec::with_capacity(HEADER_LEN + NONCE_LEN + plaintext.len() + TAG_LEN);
+    blob.extend_from_slice(MAGIC);
+    blob.push(VERSION);
+    blob.extend_from_slice(salt);
+    blob.extend_from_slice(nonce);
+    blob.extend_from_slice(plaintext);
+
+    let cipher = password_cipher(password, salt);
+    let (header, body) = blob.split_at_mut(HEADER_LEN);
+    let (nonce, ciphertext) = body.split_at_mut(NONCE_LEN);
+    let tag = cipher.gcm_seal_in_place(nonce, header, ciphertext);
+    blob.extend_from_slice(&tag);
+    blob
+}
+
+/// Decrypts a blob produced by [`seal_with_password`].
+///
+/// # Arguments
+///
+/// * `password` - The password the blob was sealed with.
+/// * `blob` - The sealed blob.
+///
+/// # Returns
+///
+/// * `Vec<u8>` - The plaintext.
+///
+/// # Errors
+///
+/// Returns `AESError::DecryptionFailed` if the password is wrong, or the blob is
+/// truncated, tampered with or not a sealed blob at all.
+pub fn open_with_password(password: &str, blob: &[u8]) -> Result<Vec<u8>, AESError> {
+    if blob.len() < HEADER_LEN + NONCE_LEN + TAG_LEN || &blob[..4] != MAGIC || blob[4] != VERSION {
+        return Err(AESError::DecryptionFailed);
+    }
+    let (header, body) = blob.split_at(HEADER_LEN);
+    let (nonce, body) = body.split_at(NONCE_LEN);
+    let (ciphertext, tag) = body.split_at(body.len() - TAG_LEN);
+
+    let cipher = password_cipher(password, &header[MAGIC.len() + 1..]);
+    let mut plaintext = ciphertext.to_vec();
+    cipher.gcm_open_in_place(nonce, header, &mut plaintext, tag.try_into().unwrap())?;
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_round_trip() {
+        let blob = seal_with_password("correct horse", b"least-effort secrets");
+        assert_eq!(&blob[..5], b"AESP\x01");
+        assert_eq!(blob.len(), HEADER_LEN + NONCE_LEN + 20 + TAG_LEN);
+        assert_eq!(open_with_password("correct horse", &blob).unwrap(), b"least-effort secrets");
+        assert_ne!(seal_with_password("correct horse", b"least-effort secrets"), blob);
+    }
+
+    #[test]
+    fn test_password_wrong_or_tampered() {
+        let blob = seal_with_password("correct horse", b"least-effort secrets");
+        assert!(matches!(open_with_password("battery staple", &blob), Err(AESError::DecryptionFailed)));
+
+        let mut salt_tampered = blob.clone();
+        salt_tampered[5] ^= 1;
+        assert!(matches!(open_with_password("correct horse", &salt_tampered), Err(AESError::DecryptionFailed)));
+        assert!(matches!(open_with_password("correct horse", &blob[..30]), Err(AESError::DecryptionFailed)));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{AESError, AESteve};
+
+/// FIPS-197 known-answer vectors: (key, plaintext, ciphertext).
+const KNOWN_ANSWERS: [(&[u8], [u8; 16], [u8; 16]); 4] = [
+    // Appendix B.
+    (
+        &[0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c],
+        [0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07, 0x34],
+        [0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a, 0x0b, 0x32],
+    ),
+    // Appendix C.1.
+    (
+        &[0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f],
+        [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff],
+        [0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a],
+    ),
+    // Appendix C.2.
+    (
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+        ],
+        [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff],
+        [0xdd, 0xa9, 0x7c, 0xa4, 0x86, 0x4c, 0xdf, 0xe0, 0x6e, 0xaf, 0x70, 0xa0, 0xec, 0x0d, 0x71, 0x91],
+    ),
+    // Appendix C.3.
+    (
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+        ],
+        [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff],
+        [0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf, 0xea, 0xfc, 0x49, 0x90, 0x4b, 0x49, 0x60, 0x89],
+    ),
+];
+
+impl AESteve {
+    /// Runs a power-on self-test of the cipher implementation.
+    ///
+    /// Checks the embedded FIPS-197 known-answer vectors for all three key sizes in both
+    /// directions, then round-trips a multi-block message through the parallel ECB path
+    /// under each key. Call it once at startup, before the cipher handles real data, the
+    /// way FIPS 140 modules run their power-on self-tests: a failure means the build or
+    /// the machine is broken and no output of this crate can be trusted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::SelfTestFailed` if any check produces an unexpected result.
+    pub fn self_test() -> Result<(), AESError> {
+        for (key, plaintext, ciphertext) in KNOWN_ANSWERS {
+            let aesteve = AESteve::new(key)?;
+            if aesteve.encrypt_array(plaintext) != ciphertext || aesteve.decrypt_array(ciphertext) != plaintext {
+                return Err(AESError::SelfTestFailed);
+            }
+
+            let message: Vec<u8> = plaintext.iter().cycle().take(100).copied().collect();
+            match aesteve.decrypt_bytes(&aesteve.encrypt_bytes(&message)) {
+                Ok(round_trip) if round_trip == message => {}
+                _ => return Err(AESError::SelfTestFailed),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test_passes() {
+        assert!(AESteve::self_test().is_ok());
+    }
+
+    #[test]
+    fn test_known_answers_cover_every_key_size() {
+        for key_len in [16, 24, 32] {
+            assert!(KNOWN_ANSWERS.iter().any(|(key, _, _)| key.len() == key_len));
+        }
+    }
+}
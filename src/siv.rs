@@ -0,0 +1,313 @@
+use crate::cmac::dbl;
+use crate::ct::{ct_eq_mask, ct_mask};
+use crate::{AESError, AESteve};
+
+const SIV_MAC_LABEL: &[u8] = b"aesteve/siv/mac";
+const SIV_CTR_LABEL: &[u8] = b"aesteve/siv/ctr";
+const SYNTHETIC_MAC_LABEL: &[u8] = b"aesteve/ctr-synthetic/mac";
+const SYNTHETIC_CTR_LABEL: &[u8] = b"aesteve/ctr-synthetic/ctr";
+const SYNTHETIC_NONCE_LABEL: &[u8] = b"aesteve/ctr-synthetic/nonce";
+
+/// Clears the bits RFC 5297 zeroes so the CTR counter can't carry into them.
+fn siv_counter(v: &[u8; 16]) -> [u8; 16] {
+    let mut q = *v;
+    q[8] &= 0x7f;
+    q[12] &= 0x7f;
+    q
+}
+
+fn xor(a: &[u8; 16], b: &[u8; 16]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    for i in 0..16 {
+        out[i] = a[i] ^ b[i];
+    }
+    out
+}
+
+impl AESteve {
+    /// S2V from RFC 5297, keyed by `self`.
+    fn s2v(&self, associated_data: &[&[u8]], plaintext: &[u8]) -> [u8; 16] {
+        let mut d = self.cmac(&[0u8; 16]);
+        for data in associated_data {
+            d = xor(&dbl(&d), &self.cmac(data));
+        }
+
+        let last = if plaintext.len() >= 16 {
+            let mut t = plaintext.to_vec();
+            let offset = t.len() - 16;
+            for (byte, mask) in t[offset..].iter_mut().zip(d) {
+                *byte ^= mask;
+            }
+            t
+        } else {
+            let mut padded = [0u8; 16];
+            padded[..plaintext.len()].copy_from_slice(plaintext);
+            padded[plaintext.len()] = 0x80;
+            xor(&dbl(&d), &padded).to_vec()
+        };
+        self.cmac(&last)
+    }
+
+    /// Encrypts `plaintext` with AES-SIV (RFC 5297), using `self` for S2V and `k2` for CTR.
+    ///
+    /// SIV is deterministic and nonce-misuse resistant: the synthetic IV is a MAC over the
+    /// associated data and plaintext, so identical inputs produce identical output.
+    ///
+    /// # Arguments
+    ///
+    /// * `k2` - The CTR key; must be independent of `self`.
+    /// * `associated_data` - Authenticated but unencrypted components, in order.
+    /// * `plaintext` - The data to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The 16-byte synthetic IV followed by the ciphertext.
+    pub fn encrypt_siv(&self, k2: &AESteve, associated_data: &[&[u8]], plaintext: &[u8]) -> Vec<u8> {
+        let v = self.s2v(associated_data, plaintext);
+        let mut output = v.to_vec();
+        output.extend_from_slice(&k2.encrypt_ctr(plaintext, &siv_counter(&v)));
+        output
+    }
+
+    /// Decrypts and authenticates output of [`AESteve::encrypt_siv`].
+    ///
+    /// # Arguments
+    ///
+    /// * `k2` - The CTR key used for encryption.
+    /// * `associated_data` - The associated data used for encryption.
+    /// * `ciphertext` - The synthetic IV followed by the ciphertext.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The plaintext.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::DecryptionFailed` if the ciphertext is truncated or fails authentication.
+    /// The two cases are deliberately indistinguishable. Authentication runs to completion
+    /// and zeroes the plaintext with a constant-time mask before the outcome is revealed.
+    pub fn decrypt_siv(&self, k2: &AESteve, associated_data: &[&[u8]], ciphertext: &[u8]) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < 16 {
+            return Err(AESError::DecryptionFailed);
+        }
+        let (v, body) = ciphertext.split_at(16);
+        let v: &[u8; 16] = v.try_into().unwrap();
+        let mut plaintext = k2.encrypt_ctr(body, &siv_counter(v));
+        let valid = ct_eq_mask(&self.s2v(associated_data, &plaintext), v);
+        ct_mask(&mut plaintext, valid);
+        if valid == 0x00 {
+            return Err(AESError::DecryptionFailed);
+        }
+        Ok(plaintext)
+    }
+
+    fn deterministic_keys(&self) -> (AESteve, AESteve) {
+        (self.derive_subkey(SIV_MAC_LABEL), self.derive_subkey(SIV_CTR_LABEL))
+    }
+
+    /// Encrypts `plaintext` deterministically, for deduplicating encrypted stores.
+    ///
+    /// Runs AES-SIV with two subkeys derived from this instance's key, each as long as that
+    /// key (so an AES-256 instance runs AES-256-SIV), with no nonce and no associated
+    /// data. Equal plaintexts always produce equal ciphertexts; beyond that (and the
+    /// plaintext length) nothing about the plaintext is revealed, unlike ECB which also
+    /// leaks repeated blocks within and across messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `plaintext` - The data to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The 16-byte synthetic IV followed by the ciphertext.
+    pub fn encrypt_deterministic(&self, plaintext: &[u8]) -> Vec<u8> {
+        let (mac_key, ctr_key) = self.deterministic_keys();
+        mac_key.encrypt_siv(&ctr_key, &[], plaintext)
+    }
+
+    /// Decrypts output of [`AESteve::encrypt_deterministic`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::DecryptionFailed` if the ciphertext is truncated or fails authentication.
+    pub fn decrypt_deterministic(&self, ciphertext: &[u8]) -> Result<Vec<u8>, AESError> {
+        let (mac_key, ctr_key) = self.deterministic_keys();
+        mac_key.decrypt_siv(&ctr_key, &[], ciphertext)
+    }
+
+    fn synthetic_keys(&self) -> (AESteve, AESteve) {
+        (self.derive_subkey(SYNTHETIC_MAC_LABEL), self.derive_subkey(SYNTHETIC_CTR_LABEL))
+    }
+
+    /// Encrypts `data` in CTR mode under a nonce derived from the plaintext itself.
+    ///
+    /// The nonce is [`AESteve::prf`] (AES-CMAC with a dedicated label) of `data`, and the
+    /// output is `nonce ‖ ciphertext` with the counter as in [`AESteve::encrypt_ctr`].
+    /// As in SIV, the MAC and the keystream use separate subkeys derived from this
+    /// instance's key, never the key itself. A lighter alternative to
+    /// [`AESteve::encrypt_deterministic`].
+    ///
+    /// Leakage: equal plaintexts give equal ciphertexts, so anyone holding two ciphertexts
+    /// learns whether the plaintexts are equal (and their lengths). That is the point for
+    /// dedup, but never use it where plaintext equality is secret. Unlike ECB, nothing
+    /// leaks about repeated blocks within or across different messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The 16-byte synthetic nonce followed by the ciphertext.
+    pub fn encrypt_ctr_synthetic(&self, data: &[u8]) -> Vec<u8> {
+        let (mac_key, ctr_key) = self.synthetic_keys();
+        let nonce = mac_key.prf(SYNTHETIC_NONCE_LABEL, data);
+        let mut output = nonce.to_vec();
+        output.extend_from_slice(&ctr_key.encrypt_ctr(data, &nonce));
+        output
+    }
+
+    /// Decrypts output of [`AESteve::encrypt_ctr_synthetic`].
+    ///
+    /// The nonce is recomputed from the decrypted plaintext and compared in constant time,
+    /// so modified ciphertext is rejected rather than returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - The synthetic nonce followed by the ciphertext.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The plaintext.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::DecryptionFailed` if the ciphertext is truncated or fails the
+    /// nonce check.
+    pub fn decrypt_ctr_synthetic(&self, ciphertext: &[u8]) -> Result<Vec<u8>, AESError> {
+        if ciphertext.len() < 16 {
+            return Err(AESError::DecryptionFailed);
+        }
+        let (mac_key, ctr_key) = self.synthetic_keys();
+        let (nonce, body) = ciphertext.split_at(16);
+        let mut plaintext = ctr_key.encrypt_ctr(body, nonce.try_into().unwrap());
+        let valid = ct_eq_mask(&mac_key.prf(SYNTHETIC_NONCE_LABEL, &plaintext), nonce);
+        ct_mask(&mut plaintext, valid);
+        if valid == 0x00 {
+            return Err(AESError::DecryptionFailed);
+        }
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len()).step_by(2).map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_siv_rfc_5297_vector() {
+        // RFC 5297, appendix A.1.
+        let k1 = AESteve::new(&hex("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0")).unwrap();
+        let k2 = AESteve::new(&hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff")).unwrap();
+        let aad = hex("101112131415161718191a1b1c1d1e1f2021222324252627");
+        let plaintext = hex("112233445566778899aabbccddee");
+        let expected = hex("85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5c");
+
+        let ciphertext = k1.encrypt_siv(&k2, &[&aad], &plaintext);
+        assert_eq!(ciphertext, expected);
+        assert_eq!(k1.decrypt_siv(&k2, &[&aad], &ciphertext).unwrap(), plaintext);
+
+        let mut tampered = ciphertext;
+        tampered[20] ^= 1;
+        assert!(matches!(k1.decrypt_siv(&k2, &[&aad], &tampered), Err(AESError::DecryptionFailed)));
+    }
+
+    #[test]
+    fn test_encrypt_deterministic() {
+        let aesteve = AESteve::new(&[0x21u8; 16]).unwrap();
+        let a = aesteve.encrypt_deterministic(b"same content");
+        let b = aesteve.encrypt_deterministic(b"same content");
+        let c = aesteve.encrypt_deterministic(b"other content");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(aesteve.decrypt_deterministic(&a).unwrap(), b"same content");
+    }
+
+    #[test]
+    fn test_deterministic_keys_match_key_size() {
+        for key_len in [16, 24, 32] {
+            let aesteve = AESteve::new(&vec![0x21u8; key_len]).unwrap();
+            let (mac_key, ctr_key) = aesteve.deterministic_keys();
+            assert_eq!(mac_key.variant(), aesteve.variant());
+            assert_eq!(ctr_key.variant(), aesteve.variant());
+
+            let ciphertext = aesteve.encrypt_deterministic(b"same content");
+            assert_eq!(aesteve.decrypt_deterministic(&ciphertext).unwrap(), b"same content");
+        }
+    }
+
+    #[test]
+    fn test_siv_failures_indistinguishable() {
+        let k1 = AESteve::new(&[1u8; 16]).unwrap();
+        let k2 = AESteve::new(&[2u8; 16]).unwrap();
+        let ciphertext = k1.encrypt_siv(&k2, &[b"header"], b"authenticated body");
+
+        let mut bad_tag = ciphertext.clone();
+        bad_tag[0] ^= 1;
+        let mut bad_body = ciphertext.clone();
+        bad_body[17] ^= 1;
+        let failures = [
+            k1.decrypt_siv(&k2, &[b"header"], &bad_tag),
+            k1.decrypt_siv(&k2, &[b"header"], &bad_body),
+            k1.decrypt_siv(&k2, &[b"header"], &ciphertext[..15]),
+            k1.decrypt_siv(&k2, &[b"header"], &ciphertext[..ciphertext.len() - 1]),
+            k1.decrypt_siv(&k2, &[b"other"], &ciphertext),
+            k1.decrypt_deterministic(&ciphertext),
+        ];
+        for failure in failures {
+            assert!(matches!(failure, Err(AESError::DecryptionFailed)));
+        }
+    }
+
+    #[test]
+    fn test_ctr_synthetic_deterministic() {
+        let aesteve = AESteve::new(&[0x33u8; 16]).unwrap();
+        let a = aesteve.encrypt_ctr_synthetic(b"same document");
+        assert_eq!(a, aesteve.encrypt_ctr_synthetic(b"same document"));
+        assert_ne!(a, aesteve.encrypt_ctr_synthetic(b"other document"));
+        assert_eq!(a.len(), 16 + 13);
+        assert_eq!(aesteve.decrypt_ctr_synthetic(&a).unwrap(), b"same document");
+
+        let repeated = aesteve.encrypt_ctr_synthetic(&b"YELLOW SUBMARINE".repeat(2));
+        assert_ne!(repeated[16..32], repeated[32..48]);
+    }
+
+    #[test]
+    fn test_ctr_synthetic_separates_keys() {
+        let aesteve = AESteve::new(&[0x33u8; 16]).unwrap();
+        let ciphertext = aesteve.encrypt_ctr_synthetic(b"same document");
+        let nonce: [u8; 16] = ciphertext[..16].try_into().unwrap();
+        assert_ne!(nonce, aesteve.prf(SYNTHETIC_NONCE_LABEL, b"same document"));
+        assert_ne!(ciphertext[16..], aesteve.encrypt_ctr(b"same document", &nonce));
+
+        let (mac_key, ctr_key) = aesteve.synthetic_keys();
+        assert_eq!(nonce, mac_key.prf(SYNTHETIC_NONCE_LABEL, b"same document"));
+        assert_eq!(ciphertext[16..], ctr_key.encrypt_ctr(b"same document", &nonce));
+    }
+
+    #[test]
+    fn test_ctr_synthetic_rejects_tampering() {
+        let aesteve = AESteve::new(&[0x33u8; 16]).unwrap();
+        let ciphertext = aesteve.encrypt_ctr_synthetic(b"same document");
+        for i in [0, 15, 16, ciphertext.len() - 1] {
+            let mut tampered = ciphertext.clone();
+            tampered[i] ^= 1;
+            assert!(matches!(aesteve.decrypt_ctr_synthetic(&tampered), Err(AESError::DecryptionFailed)));
+        }
+        assert!(matches!(aesteve.decrypt_ctr_synthetic(&ciphertext[..15]), Err(AESError::DecryptionFailed)));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::envelope::{detect_variant, envelope_header, HEADER_LEN};
+use crate::{random_array, AESError, AESteve};
+
+/// The mode byte following the envelope header: AES-GCM with a random 96-bit nonce and
+/// a 128-bit tag.
+const MODE_GCM: u8 = 1;
+const SMART_HEADER_LEN: usize = HEADER_LEN + 1;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+impl AESteve {
+    /// Encrypts `data` with the crate's recommended defaults: the safe front door.
+    ///
+    /// Always uses AES-GCM with this instance's key size, whatever the size of `data`,
+    /// under a fresh random 96-bit nonce from the operating system. The output is the
+    /// envelope header `b"AESV" ‖ version ‖ variant`, a mode byte, then
+    /// `nonce ‖ ciphertext ‖ tag`: 35 bytes longer than `data`, with the header and mode
+    /// byte authenticated as associated data. The header records the key size, so
+    /// [`detect_variant`] reads it like any other envelope, and the mode byte fixes every
+    /// other parameter. Builder settings such as `with_encoding`, `with_checksum` and
+    /// `with_envelope` do not apply.
+    ///
+    /// Random nonces are safe for up to 2^32 messages under one key.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The sealed message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system's random number generator is unavailable, or if
+    /// `data` is longer than AES-GCM allows (`2^32 - 2` blocks).
+    pub fn encrypt_smart(&self, data: &[u8]) -> Vec<u8> {
+        let nonce: [u8; NONCE_LEN] = random_array();
+        let mut sealed = Vec::with_capacity(SMART_HEADER_LEN + NONCE_LEN + data.len() + TAG_LEN);
+        sealed.extend_from_slice(&envelope_header(self.variant()));
+        sealed.push(MODE_GCM);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(data);
+
+        let (header, ciphertext) = sealed.split_at_mut(SMART_HEADER_LEN + NONCE_LEN);
+        let tag = self.gcm_seal_in_place(&nonce, &header[..SMART_HEADER_LEN], ciphertext);
+        sealed.extend_from_slice(&tag);
+        sealed
+    }
+
+    /// Decrypts a message produced by [`AESteve::encrypt_smart`].
+    ///
+    /// # Arguments
+    ///
+    /// * `sealed` - The sealed message.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The plaintext.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::DecryptionFailed` if the key is wrong or of a different size, or
+    /// the message is truncated, tampered with or not from `encrypt_smart` at all.
+    pub fn decrypt_smart(&self, sealed: &[u8]) -> Result<Vec<u8>, AESError> {
+        if sealed.len() < SMART_HEADER_LEN + NONCE_LEN + TAG_LEN
+            || detect_variant(sealed).ok() != Some(self.variant())
+            || sealed[HEADER_LEN] != MODE_GCM
+        {
+            return Err(AESError::DecryptionFailed);
+        }
+        let (header, body) = sealed.split_at(SMART_HEADER_LEN);
+        let (nonce, body) = body.split_at(NONCE_LEN);
+        let (ciphertext, tag) = body.split_at(body.len() - TAG_LEN);
+
+        let mut plaintext = ciphertext.to_vec();
+        self.gcm_open_in_place(nonce, header, &mut plaintext, tag.try_into().unwrap())?;
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AesVariant;
+
+    #[test]
+    fn test_smart_round_trip() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        for data in [&b""[..], b"short", &[0x42u8; 1000]] {
+            let sealed = aesteve.encrypt_smart(data);
+            assert_eq!(&sealed[..HEADER_LEN], envelope_header(AesVariant::Aes128));
+            assert_eq!(sealed[HEADER_LEN], MODE_GCM);
+            assert_eq!(sealed.len(), data.len() + SMART_HEADER_LEN + NONCE_LEN + TAG_LEN);
+            assert_eq!(aesteve.decrypt_smart(&sealed).unwrap(), data);
+        }
+        assert_ne!(aesteve.encrypt_smart(b"short"), aesteve.encrypt_smart(b"short"));
+    }
+
+    #[test]
+    fn test_smart_records_variant() {
+        for key in [&[7u8; 16][..], &[7u8; 24], &[7u8; 32]] {
+            let aesteve = AESteve::new(key).unwrap();
+            let sealed = aesteve.encrypt_smart(b"which key size?");
+            assert_eq!(detect_variant(&sealed).unwrap(), aesteve.variant());
+            assert_eq!(aesteve.decrypt_smart(&sealed).unwrap(), b"which key size?");
+        }
+    }
+
+    #[test]
+    fn test_smart_detects_tampering() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        let sealed = aesteve.encrypt_smart(b"do the right thing");
+        for i in [0, 4, 5, HEADER_LEN, SMART_HEADER_LEN, SMART_HEADER_LEN + NONCE_LEN, sealed.len() - 1] {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert!(matches!(aesteve.decrypt_smart(&tampered), Err(AESError::DecryptionFailed)));
+        }
+        assert!(matches!(aesteve.decrypt_smart(&sealed[..20]), Err(AESError::DecryptionFailed)));
+
+        let other = AESteve::new(&[1u8; 16]).unwrap();
+        assert!(matches!(other.decrypt_smart(&sealed), Err(AESError::DecryptionFailed)));
+        let longer = AESteve::new(&[0u8; 32]).unwrap();
+        assert!(matches!(longer.decrypt_smart(&sealed), Err(AESError::DecryptionFailed)));
+    }
+}
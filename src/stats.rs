@@ -0,0 +1,62 @@
+use crate::{AESError, AESteve};
+use std::time::{Duration, Instant};
+
+/// Metrics for a single call to [`AESteve::encrypt_with_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncryptStats {
+    /// The number of 16-byte blocks encrypted, including the padding block.
+    pub blocks: usize,
+    /// The length of the plaintext message in bytes.
+    pub bytes: usize,
+    /// The wall time spent encrypting and encoding.
+    pub duration: Duration,
+}
+
+impl AESteve {
+    /// Encrypts the given message like [`AESteve::encrypt_ecb`] and reports what it cost.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to be encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `(String, EncryptStats)` - The encrypted message in the configured encoding, and
+    ///   the block count, plaintext size and wall time of the call.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError` if an error occurs during encryption.
+    pub fn encrypt_with_stats(&self, message: String) -> Result<(String, EncryptStats), AESError> {
+        let start = Instant::now();
+        let encrypted_message = self.encrypt_bytes(message.as_bytes());
+        let encoded = self.encoding.encode(&encrypted_message);
+        let stats = EncryptStats {
+            // The envelope header, when enabled, is shorter than a block.
+            blocks: encrypted_message.len() / 16,
+            bytes: message.len(),
+            duration: start.elapsed(),
+        };
+        Ok((encoded, stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_with_stats() {
+        let aesteve = AESteve::new(&[0u8; 16]).unwrap();
+        for (len, blocks) in [(0, 1), (15, 1), (16, 2), (100, 7)] {
+            let message = "x".repeat(len);
+            let (encrypted_message, stats) = aesteve.encrypt_with_stats(message.clone()).unwrap();
+            assert_eq!(stats.bytes, len);
+            assert_eq!(stats.blocks, blocks);
+            assert_eq!(aesteve.decrypt_ecb(encrypted_message).unwrap(), message);
+        }
+
+        let enveloped = aesteve.with_envelope(true);
+        assert_eq!(enveloped.encrypt_with_stats(String::from("x")).unwrap().1.blocks, 1);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{AESError, AESteve};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+impl AESteve {
+    /// Serializes `value` to JSON and encrypts it like [`AESteve::encrypt_ecb`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to be serialized and encrypted.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The encrypted value in the configured encoding (Base64 by default).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::Serialization` if `value` cannot be serialized to JSON.
+    pub fn encrypt_value<T: Serialize + ?Sized>(&self, value: &T) -> Result<String, AESError> {
+        let json = serde_json::to_vec(value)?;
+        Ok(self.encoding.encode(&self.encrypt_bytes(&json)))
+    }
+
+    /// Decrypts output of [`AESteve::encrypt_value`] and deserializes it as `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted_value` - The encrypted value in the configured encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The deserialized value.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error [`AESteve::decrypt_ecb`] can, or `AESError::Serialization` if the
+    /// decrypted bytes are not valid JSON for `T`.
+    pub fn decrypt_value<T: DeserializeOwned>(&self, encrypted_value: &str) -> Result<T, AESError> {
+        let json = self.decrypt_bytes(&self.decode_ciphertext(encrypted_value)?)?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Session {
+        user_id: u64,
+        roles: Vec<String>,
+        expires: Option<String>,
+    }
+
+    #[test]
+    fn test_value_round_trip() {
+        let aesteve = AESteve::new(&[5u8; 16]).unwrap();
+        let session = Session {
+            user_id: 42,
+            roles: vec![String::from("admin"), String::from("ops")],
+            expires: None,
+        };
+        let encrypted = aesteve.encrypt_value(&session).unwrap();
+        assert_eq!(aesteve.decrypt_value::<Session>(&encrypted).unwrap(), session);
+    }
+
+    #[test]
+    fn test_value_wrong_type() {
+        let aesteve = AESteve::new(&[5u8; 16]).unwrap();
+        let encrypted = aesteve.encrypt_value("not a session").unwrap();
+        assert!(matches!(aesteve.decrypt_value::<Session>(&encrypted), Err(AESError::Serialization(_))));
+    }
+}
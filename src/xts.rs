@@ -0,0 +1,314 @@
+use crate::{AESError, AESteve};
+
+/// Sector sizes accepted by [`AESteve::encrypt_sector`], in bytes.
+const SECTOR_SIZES: [usize; 4] = [512, 1024, 2048, 4096];
+
+/// Multiplies a tweak by the primitive element α of GF(2^128), in IEEE 1619 byte order.
+fn mul_alpha(tweak: &[u8; 16]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    let mut carry = 0u8;
+    for i in 0..16 {
+        out[i] = (tweak[i] << 1) | carry;
+        carry = tweak[i] >> 7;
+    }
+    if carry != 0 {
+        out[0] ^= 0x87;
+    }
+    out
+}
+
+fn xor_block(a: &[u8; 16], b: &[u8; 16]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    for i in 0..16 {
+        out[i] = a[i] ^ b[i];
+    }
+    out
+}
+
+impl AESteve {
+    /// Runs the XTS data path with `self` as the data key, starting from `tweak`.
+    ///
+    /// Inputs that are not block-aligned use ciphertext stealing: the last full block and
+    /// the trailing partial block are processed together so the output keeps the input length.
+    fn xts(&self, data: &[u8], tweak: [u8; 16], encrypt: bool) -> Result<Vec<u8>, AESError> {
+        if data.len() < 16 {
+            return Err(AESError::InvalidDataLength);
+        }
+        let xex = |block: &[u8; 16], tweak: &[u8; 16]| {
+            let masked = xor_block(block, tweak);
+            let processed = if encrypt {
+                self.encrypt_block_bytes(&masked)
+            } else {
+                self.decrypt_block_bytes(&masked)
+            };
+            xor_block(&processed, tweak)
+        };
+
+        let remainder = data.len() % 16;
+        let simple_len = if remainder == 0 { data.len() } else { data.len() - remainder - 16 };
+
+        let mut output = Vec::with_capacity(data.len());
+        let mut tweak = tweak;
+        for chunk in data[..simple_len].chunks_exact(16) {
+            output.extend_from_slice(&xex(chunk.try_into().unwrap(), &tweak));
+            tweak = mul_alpha(&tweak);
+        }
+
+        if remainder != 0 {
+            let last_full: &[u8; 16] = data[simple_len..simple_len + 16].try_into().unwrap();
+            let tail = &data[simple_len + 16..];
+            let next_tweak = mul_alpha(&tweak);
+            let (first_tweak, second_tweak) = if encrypt { (tweak, next_tweak) } else { (next_tweak, tweak) };
+
+            let stolen = xex(last_full, &first_tweak);
+            let mut combined = stolen;
+            combined[..remainder].copy_from_slice(tail);
+            output.extend_from_slice(&xex(&combined, &second_tweak));
+            output.extend_from_slice(&stolen[..remainder]);
+        }
+        Ok(output)
+    }
+
+    fn xex(&self, data: &[u8], sector: u64, encrypt: bool) -> Result<Vec<u8>, AESError> {
+        if !data.len().is_multiple_of(16) {
+            return Err(AESError::InvalidDataLength);
+        }
+        let tweak = self.encrypt_block_bytes(&(sector as u128).to_le_bytes());
+        self.xts(data, tweak, encrypt)
+    }
+
+    /// Encrypts a sector in XEX mode (XOR-encrypt-XOR) using a single key.
+    ///
+    /// The tweak is `E_k(sector)`, multiplied by α in GF(2^128) for each successive block,
+    /// and is XORed into every block before and after encryption. Identical plaintext
+    /// therefore encrypts differently in every sector and at every block position.
+    /// Unlike XTS there is no ciphertext stealing, so `data` must be block-aligned.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The sector contents; a nonzero multiple of 16 bytes.
+    /// * `sector` - The sector number used as the tweak.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The encrypted sector, the same length as `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidDataLength` if `data` is empty or not a multiple of 16 bytes.
+    pub fn encrypt_xex(&self, data: &[u8], sector: u64) -> Result<Vec<u8>, AESError> {
+        self.xex(data, sector, true)
+    }
+
+    /// Decrypts a sector encrypted with [`AESteve::encrypt_xex`].
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The encrypted sector; a nonzero multiple of 16 bytes.
+    /// * `sector` - The sector number the data was encrypted under.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted sector.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidDataLength` if `data` is empty or not a multiple of 16 bytes.
+    pub fn decrypt_xex(&self, data: &[u8], sector: u64) -> Result<Vec<u8>, AESError> {
+        self.xex(data, sector, false)
+    }
+
+    /// Encrypts a sector with XTS-AES (IEEE 1619), using `self` as the data key.
+    ///
+    /// Each key runs at its own instance's key size: two 16-byte keys give XTS-AES-128 and
+    /// two 32-byte keys XTS-AES-256.
+    ///
+    /// The tweak is `E_{k2}(sector)` with the sector number encoded little-endian, advanced by
+    /// α in GF(2^128) per block. Sectors that are not a multiple of 16 bytes are handled with
+    /// ciphertext stealing, so the output is always the same length as `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `k2` - The tweak key; must be independent of the data key.
+    /// * `data` - The sector contents; at least 16 bytes.
+    /// * `sector` - The sector (data unit) number.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The encrypted sector.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidDataLength` if `data` is shorter than 16 bytes.
+    pub fn encrypt_xts(&self, k2: &AESteve, data: &[u8], sector: u128) -> Result<Vec<u8>, AESError> {
+        self.xts(data, k2.encrypt_block_bytes(&sector.to_le_bytes()), true)
+    }
+
+    /// Decrypts a sector encrypted with [`AESteve::encrypt_xts`].
+    ///
+    /// # Arguments
+    ///
+    /// * `k2` - The tweak key used for encryption.
+    /// * `data` - The encrypted sector; at least 16 bytes.
+    /// * `sector` - The sector (data unit) number.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted sector.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidDataLength` if `data` is shorter than 16 bytes.
+    pub fn decrypt_xts(&self, k2: &AESteve, data: &[u8], sector: u128) -> Result<Vec<u8>, AESError> {
+        self.xts(data, k2.encrypt_block_bytes(&sector.to_le_bytes()), false)
+    }
+
+    /// Encrypts one sector of an emulated block device with XTS-AES at this instance's key size.
+    ///
+    /// Equivalent to [`AESteve::encrypt_xts`] with `sector_num` as the tweak, but only
+    /// accepts whole sectors of 512, 1024, 2048 or 4096 bytes, so a buffer of the wrong
+    /// size is rejected instead of silently encrypted.
+    ///
+    /// # Arguments
+    ///
+    /// * `k2` - The tweak key; must be independent of the data key.
+    /// * `sector_num` - The sector number.
+    /// * `sector_data` - The sector contents.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The encrypted sector, the same length as `sector_data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidDataLength` if `sector_data` is not a supported sector size.
+    pub fn encrypt_sector(&self, k2: &AESteve, sector_num: u64, sector_data: &[u8]) -> Result<Vec<u8>, AESError> {
+        if !SECTOR_SIZES.contains(&sector_data.len()) {
+            return Err(AESError::InvalidDataLength);
+        }
+        self.encrypt_xts(k2, sector_data, sector_num.into())
+    }
+
+    /// Decrypts a sector encrypted with [`AESteve::encrypt_sector`].
+    ///
+    /// # Arguments
+    ///
+    /// * `k2` - The tweak key used for encryption.
+    /// * `sector_num` - The sector number.
+    /// * `sector_data` - The encrypted sector.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The decrypted sector.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AESError::InvalidDataLength` if `sector_data` is not a supported sector size.
+    pub fn decrypt_sector(&self, k2: &AESteve, sector_num: u64, sector_data: &[u8]) -> Result<Vec<u8>, AESError> {
+        if !SECTOR_SIZES.contains(&sector_data.len()) {
+            return Err(AESError::InvalidDataLength);
+        }
+        self.decrypt_xts(k2, sector_data, sector_num.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len()).step_by(2).map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_xex_round_trip_across_sectors() {
+        let aesteve = AESteve::new(&[9u8; 16]).unwrap();
+        let data = [0x5Au8; 64];
+
+        let sector_1 = aesteve.encrypt_xex(&data, 1).unwrap();
+        let sector_2 = aesteve.encrypt_xex(&data, 2).unwrap();
+        assert_ne!(sector_1, sector_2);
+        assert_ne!(sector_1[..16], sector_1[16..32]);
+
+        assert_eq!(aesteve.decrypt_xex(&sector_1, 1).unwrap(), data);
+        assert_eq!(aesteve.decrypt_xex(&sector_2, 2).unwrap(), data);
+        assert_ne!(aesteve.decrypt_xex(&sector_1, 2).unwrap(), data);
+    }
+
+    #[test]
+    fn test_xex_requires_aligned_input() {
+        let aesteve = AESteve::new(&[9u8; 16]).unwrap();
+        assert!(matches!(aesteve.encrypt_xex(&[0u8; 20], 0), Err(AESError::InvalidDataLength)));
+        assert!(matches!(aesteve.decrypt_xex(&[], 0), Err(AESError::InvalidDataLength)));
+    }
+
+    #[test]
+    fn test_xts_ieee_1619_vectors() {
+        // IEEE 1619-2007, vectors 1 and 2.
+        let k1 = AESteve::new(&[0x00; 16]).unwrap();
+        let k2 = AESteve::new(&[0x00; 16]).unwrap();
+        let expected = hex("917cf69ebd68b2ec9b9fe9a3eadda692cd43d2f59598ed858c02c2652fbf922e");
+        assert_eq!(k1.encrypt_xts(&k2, &[0u8; 32], 0).unwrap(), expected);
+
+        let k1 = AESteve::new(&[0x11; 16]).unwrap();
+        let k2 = AESteve::new(&[0x22; 16]).unwrap();
+        let expected = hex("c454185e6a16936e39334038acef838bfb186fff7480adc4289382ecd6d394f0");
+        assert_eq!(k1.encrypt_xts(&k2, &[0x44; 32], 0x3333333333).unwrap(), expected);
+        assert_eq!(k1.decrypt_xts(&k2, &expected, 0x3333333333).unwrap(), [0x44; 32]);
+    }
+
+    #[test]
+    fn test_xts_ciphertext_stealing() {
+        // Generated with OpenSSL's aes-128-xts (via Python `cryptography`).
+        let k1 = AESteve::new(&hex("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0")).unwrap();
+        let k2 = AESteve::new(&hex("bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0")).unwrap();
+        let cases = [
+            (17, "641610679dcbf92e505c41333fb06c2a95"),
+            (20, "a8ba0048d75084603eb8423a09b7bf7595c871f6"),
+        ];
+        for (len, expected) in cases {
+            let plaintext: Vec<u8> = (0..len as u8).collect();
+            let ciphertext = k1.encrypt_xts(&k2, &plaintext, 0x9a78563412).unwrap();
+            assert_eq!(ciphertext, hex(expected));
+            assert_eq!(k1.decrypt_xts(&k2, &ciphertext, 0x9a78563412).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_xts_requires_full_block() {
+        let k1 = AESteve::new(&[1u8; 16]).unwrap();
+        let k2 = AESteve::new(&[2u8; 16]).unwrap();
+        assert!(matches!(k1.encrypt_xts(&k2, &[0u8; 15], 0), Err(AESError::InvalidDataLength)));
+    }
+
+    #[test]
+    fn test_sector_numbers_change_ciphertext() {
+        let k1 = AESteve::new(&[1u8; 16]).unwrap();
+        let k2 = AESteve::new(&[2u8; 16]).unwrap();
+        let sector_a = [0xAAu8; 512];
+        let sector_b = [0xBBu8; 512];
+
+        let a_at_7 = k1.encrypt_sector(&k2, 7, &sector_a).unwrap();
+        let b_at_8 = k1.encrypt_sector(&k2, 8, &sector_b).unwrap();
+        assert_ne!(k1.encrypt_sector(&k2, 8, &sector_a).unwrap(), a_at_7);
+        assert_ne!(k1.encrypt_sector(&k2, 7, &sector_b).unwrap(), b_at_8);
+        assert_eq!(a_at_7, k1.encrypt_xts(&k2, &sector_a, 7).unwrap());
+
+        assert_eq!(k1.decrypt_sector(&k2, 7, &a_at_7).unwrap(), sector_a);
+        assert_eq!(k1.decrypt_sector(&k2, 8, &b_at_8).unwrap(), sector_b);
+        assert_ne!(k1.decrypt_sector(&k2, 8, &a_at_7).unwrap(), sector_a);
+    }
+
+    #[test]
+    fn test_sector_size_validation() {
+        let k1 = AESteve::new(&[1u8; 16]).unwrap();
+        let k2 = AESteve::new(&[2u8; 16]).unwrap();
+        for size in SECTOR_SIZES {
+            assert_eq!(k1.encrypt_sector(&k2, 0, &vec![0u8; size]).unwrap().len(), size);
+        }
+        for size in [0, 16, 511, 513, 8192] {
+            assert!(matches!(k1.encrypt_sector(&k2, 0, &vec![0u8; size]), Err(AESError::InvalidDataLength)));
+            assert!(matches!(k1.decrypt_sector(&k2, 0, &vec![0u8; size]), Err(AESError::InvalidDataLength)));
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Checks that `encrypt_ctr_into` never allocates.
+//!
+//! Lives in its own test binary because it installs a counting global allocator, which
+//! would otherwise apply to every unit test in the library.
+
+use aesteve::AESteve;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts allocations per thread, so the harness's own threads do not disturb the count.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+#[test]
+fn test_ctr_into_allocation_free() {
+    let aesteve = AESteve::new(&[0x2bu8; 16]).unwrap();
+    let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+    let mut out = vec![0u8; data.len()];
+    let mut scratch = [0u8; 16];
+
+    let before = ALLOCATIONS.with(Cell::get);
+    aesteve.encrypt_ctr_into(&data, &[0xff; 16], &mut out, &mut scratch).unwrap();
+    assert_eq!(ALLOCATIONS.with(Cell::get), before);
+    assert_eq!(out, aesteve.encrypt_ctr(&data, &[0xff; 16]));
+}
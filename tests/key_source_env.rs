@@ -0,0 +1,20 @@
+//! Checks that `AESteve::from_env` reads its key from the process environment.
+//!
+//! Lives in its own test binary with a single test because it sets environment variables,
+//! which races with any other thread reading the environment at the same time.
+
+use aesteve::AESteve;
+
+#[test]
+fn test_from_env_reads_variable() {
+    let key: Vec<u8> = (0u8..16).collect();
+    let expected = AESteve::new(&key).unwrap().encrypt_array([0x42u8; 16]);
+
+    let name = format!("AESTEVE_TEST_ENV_KEY_{}", std::process::id());
+    for value in ["000102030405060708090a0b0c0d0e0f", "AAECAwQFBgcICQoLDA0ODw==\n"] {
+        std::env::set_var(&name, value);
+        let from_env = AESteve::from_env(&name).unwrap();
+        assert_eq!(from_env.encrypt_array([0x42u8; 16]), expected);
+    }
+    std::env::remove_var(&name);
+}